@@ -1,11 +1,18 @@
 use log::trace;
 
-use crate::{Bitness, Info, Type};
+use crate::{architecture, uname::uname, Bitness, Detector, Info, Provenance, Type};
 
-pub fn current_platform() -> Info {
-    trace!("android::current_platform is called");
+pub fn current_platform(detector: &Detector) -> Info {
+    trace!("android::current_platform({:?}) is called", detector);
 
-    let info = Info::with_type(Type::Android);
+    let mut provenance = Provenance::default();
+    provenance.record_target();
+
+    let mut info = Info::with_type(Type::Android);
+    info.architecture = architecture::get();
+    info.kernel = uname().ok();
+    provenance.select(&info);
+    info.provenance = provenance;
     trace!("Returning {:?}", info);
     info
 }
@@ -17,7 +24,7 @@ mod tests {
 
     #[test]
     fn os_type() {
-        let version = current_platform();
+        let version = current_platform(&Detector::default());
         assert_eq!(Type::Android, version.os_type());
     }
 }
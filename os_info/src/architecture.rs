@@ -0,0 +1,501 @@
+// spell-checker:ignore aarch, armhf, armel, armv, earmv, loongarch, loong, mipsel, powerpc, riscv
+// spell-checker:ignore sparc, sparcv, ppcle, hwcap, amd, armhfp
+
+use std::fmt::{self, Display, Formatter};
+#[cfg(target_os = "linux")]
+use std::{fs::File, io::Read, path::Path};
+
+use super::Bitness;
+
+/// Processor architecture of the operating system.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(non_camel_case_types)]
+#[non_exhaustive]
+pub enum Architecture {
+    /// Unknown architecture (unable to determine).
+    #[default]
+    Unknown,
+    /// 32-bit x86 (`i386`, `i686`).
+    X86,
+    /// 64-bit x86 (`x86_64`, `amd64`).
+    X86_64,
+    /// 64-bit ARM (`aarch64`, `arm64`).
+    Aarch64,
+    /// 32-bit ARM of an unspecified version, including soft-float ABI systems (`armel`).
+    Arm,
+    /// ARMv6 (`armv6l`).
+    Armv6,
+    /// ARMv7 with the hard-float ABI (`armv7l`, `armhf`).
+    Armv7,
+    /// 32-bit RISC-V.
+    Riscv32,
+    /// 64-bit RISC-V.
+    Riscv64,
+    /// 32-bit big-endian PowerPC.
+    Ppc,
+    /// 64-bit big-endian PowerPC.
+    Ppc64,
+    /// 64-bit little-endian PowerPC.
+    Ppc64le,
+    /// 64-bit IBM Z.
+    S390x,
+    /// 32-bit big-endian MIPS.
+    Mips,
+    /// 32-bit little-endian MIPS.
+    Mipsel,
+    /// 64-bit big-endian MIPS.
+    Mips64,
+    /// 64-bit little-endian MIPS.
+    Mips64el,
+    /// 64-bit LoongArch.
+    Loongarch64,
+    /// 32-bit SPARC.
+    Sparc,
+    /// 64-bit SPARC.
+    Sparc64,
+}
+
+impl Architecture {
+    /// Parses the machine name reported by `uname -m` or the `hw.machine_arch` value of `sysctl`.
+    /// Debian and RPM architecture names are recognized as well.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Architecture;
+    ///
+    /// assert_eq!(Architecture::X86_64, Architecture::from_machine("x86_64"));
+    /// assert_eq!(Architecture::X86_64, Architecture::from_machine("amd64"));
+    /// assert_eq!(Architecture::Armv7, Architecture::from_machine("armv7l"));
+    /// assert_eq!(Architecture::Unknown, Architecture::from_machine("vax"));
+    /// ```
+    pub fn from_machine(machine: &str) -> Self {
+        match machine.trim().to_lowercase().as_str() {
+            "i386" | "i486" | "i586" | "i686" | "x86" | "i86pc" => Architecture::X86,
+            "x86_64" | "amd64" | "x64" => Architecture::X86_64,
+            "aarch64" | "arm64" | "arm64e" | "aarch64_be" => Architecture::Aarch64,
+            "armv7" | "armv7l" | "armv7hl" | "armv7a" | "armhf" | "armhfp" | "earmv7hf" => {
+                Architecture::Armv7
+            }
+            "armv6" | "armv6l" | "armv6hl" | "earmv6hf" => Architecture::Armv6,
+            "arm" | "armel" | "armv5tel" | "armv5l" | "earm" => Architecture::Arm,
+            "riscv32" => Architecture::Riscv32,
+            "riscv64" | "riscv" => Architecture::Riscv64,
+            "ppc" | "powerpc" => Architecture::Ppc,
+            "ppc64" | "powerpc64" => Architecture::Ppc64,
+            "ppc64le" | "ppc64el" | "powerpc64le" => Architecture::Ppc64le,
+            "s390x" => Architecture::S390x,
+            "mips" | "mipseb" => Architecture::Mips,
+            "mipsel" => Architecture::Mipsel,
+            "mips64" | "mips64eb" => Architecture::Mips64,
+            "mips64el" => Architecture::Mips64el,
+            "loongarch64" | "loong64" => Architecture::Loongarch64,
+            "sparc" => Architecture::Sparc,
+            "sparc64" | "sparcv9" | "sun4v" | "sun4u" => Architecture::Sparc64,
+            _ => Architecture::Unknown,
+        }
+    }
+
+    /// Returns the architecture name used by Debian packages, if Debian supports it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Architecture;
+    ///
+    /// assert_eq!(Some("amd64"), Architecture::X86_64.debian_name());
+    /// assert_eq!(Some("arm64"), Architecture::Aarch64.debian_name());
+    /// assert_eq!(Some("armhf"), Architecture::Armv7.debian_name());
+    /// ```
+    pub fn debian_name(self) -> Option<&'static str> {
+        match self {
+            Architecture::X86 => Some("i386"),
+            Architecture::X86_64 => Some("amd64"),
+            Architecture::Aarch64 => Some("arm64"),
+            Architecture::Arm | Architecture::Armv6 => Some("armel"),
+            Architecture::Armv7 => Some("armhf"),
+            Architecture::Riscv64 => Some("riscv64"),
+            Architecture::Ppc => Some("powerpc"),
+            Architecture::Ppc64 => Some("ppc64"),
+            Architecture::Ppc64le => Some("ppc64el"),
+            Architecture::S390x => Some("s390x"),
+            Architecture::Mips => Some("mips"),
+            Architecture::Mipsel => Some("mipsel"),
+            Architecture::Mips64el => Some("mips64el"),
+            Architecture::Loongarch64 => Some("loong64"),
+            Architecture::Sparc64 => Some("sparc64"),
+            Architecture::Unknown
+            | Architecture::Riscv32
+            | Architecture::Mips64
+            | Architecture::Sparc => None,
+        }
+    }
+
+    /// Returns the architecture name used by RPM packages.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Architecture;
+    ///
+    /// assert_eq!(Some("x86_64"), Architecture::X86_64.rpm_name());
+    /// assert_eq!(Some("aarch64"), Architecture::Aarch64.rpm_name());
+    /// assert_eq!(Some("i686"), Architecture::X86.rpm_name());
+    /// ```
+    pub fn rpm_name(self) -> Option<&'static str> {
+        match self {
+            Architecture::X86 => Some("i686"),
+            Architecture::X86_64 => Some("x86_64"),
+            Architecture::Aarch64 => Some("aarch64"),
+            Architecture::Arm => Some("armv5tel"),
+            Architecture::Armv6 => Some("armv6hl"),
+            Architecture::Armv7 => Some("armv7hl"),
+            Architecture::Riscv64 => Some("riscv64"),
+            Architecture::Ppc => Some("ppc"),
+            Architecture::Ppc64 => Some("ppc64"),
+            Architecture::Ppc64le => Some("ppc64le"),
+            Architecture::S390x => Some("s390x"),
+            Architecture::Mips => Some("mips"),
+            Architecture::Mipsel => Some("mipsel"),
+            Architecture::Mips64 => Some("mips64"),
+            Architecture::Mips64el => Some("mips64el"),
+            Architecture::Loongarch64 => Some("loongarch64"),
+            Architecture::Sparc => Some("sparc"),
+            Architecture::Sparc64 => Some("sparc64"),
+            Architecture::Unknown | Architecture::Riscv32 => None,
+        }
+    }
+
+    /// Returns the bitness of the architecture.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Architecture, Bitness};
+    ///
+    /// assert_eq!(Bitness::X64, Architecture::Ppc64le.bitness());
+    /// assert_eq!(Bitness::X32, Architecture::Armv7.bitness());
+    /// ```
+    pub fn bitness(self) -> Bitness {
+        match self {
+            Architecture::Unknown => Bitness::Unknown,
+            Architecture::X86
+            | Architecture::Arm
+            | Architecture::Armv6
+            | Architecture::Armv7
+            | Architecture::Riscv32
+            | Architecture::Ppc
+            | Architecture::Mips
+            | Architecture::Mipsel
+            | Architecture::Sparc => Bitness::X32,
+            Architecture::X86_64
+            | Architecture::Aarch64
+            | Architecture::Riscv64
+            | Architecture::Ppc64
+            | Architecture::Ppc64le
+            | Architecture::S390x
+            | Architecture::Mips64
+            | Architecture::Mips64el
+            | Architecture::Loongarch64
+            | Architecture::Sparc64 => Bitness::X64,
+        }
+    }
+}
+
+impl Display for Architecture {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Architecture::Unknown => "unknown architecture",
+            Architecture::X86 => "x86",
+            Architecture::X86_64 => "x86_64",
+            Architecture::Aarch64 => "aarch64",
+            Architecture::Arm => "arm",
+            Architecture::Armv6 => "armv6",
+            Architecture::Armv7 => "armv7",
+            Architecture::Riscv32 => "riscv32",
+            Architecture::Riscv64 => "riscv64",
+            Architecture::Ppc => "ppc",
+            Architecture::Ppc64 => "ppc64",
+            Architecture::Ppc64le => "ppc64le",
+            Architecture::S390x => "s390x",
+            Architecture::Mips => "mips",
+            Architecture::Mipsel => "mipsel",
+            Architecture::Mips64 => "mips64",
+            Architecture::Mips64el => "mips64el",
+            Architecture::Loongarch64 => "loongarch64",
+            Architecture::Sparc => "sparc",
+            Architecture::Sparc64 => "sparc64",
+        })
+    }
+}
+
+#[cfg(any(target_os = "android", target_os = "linux", target_os = "macos"))]
+pub fn get() -> Architecture {
+    crate::uname::uname()
+        .map(|kernel| Architecture::from_machine(kernel.machine()))
+        .ok()
+        .unwrap_or_default()
+}
+
+/// The BSDs report the platform rather than the processor in `uname -m` (for example, `evbarm` or
+/// `macppc`), so the architecture of the userland is read using the `sysctl` system call instead.
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub fn get() -> Architecture {
+    crate::sysctl::sysctl(crate::sysctl::MACHINE_ARCH)
+        .map(|arch| Architecture::from_machine(&arch))
+        .unwrap_or_default()
+}
+
+/// Determines the architecture from the header of the ELF executable at the given path.
+#[cfg(target_os = "linux")]
+pub fn from_elf(path: &Path) -> Architecture {
+    // Enough for `e_flags` of a 32-bit header, which tells the ARM float ABI.
+    let mut header = Vec::with_capacity(40);
+    match File::open(path).and_then(|file| file.take(40).read_to_end(&mut header)) {
+        Ok(_) => elf_machine(&header),
+        Err(e) => {
+            log::trace!("Unable to read ELF header of {:?}: {:?}", path, e);
+            Architecture::Unknown
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn elf_machine(header: &[u8]) -> Architecture {
+    const EM_SPARC: u16 = 2;
+    const EM_386: u16 = 3;
+    const EM_MIPS: u16 = 8;
+    const EM_PPC: u16 = 20;
+    const EM_PPC64: u16 = 21;
+    const EM_S390: u16 = 22;
+    const EM_ARM: u16 = 40;
+    const EM_SPARCV9: u16 = 43;
+    const EM_X86_64: u16 = 62;
+    const EM_AARCH64: u16 = 183;
+    const EM_RISCV: u16 = 243;
+    const EM_LOONGARCH: u16 = 258;
+    const EF_ARM_ABI_FLOAT_HARD: u32 = 0x400;
+
+    let (class, data, machine) = match header {
+        [0x7f, b'E', b'L', b'F', class, data, ..] if header.len() >= 20 => {
+            let machine = [header[18], header[19]];
+            let machine = match data {
+                1 => u16::from_le_bytes(machine),
+                2 => u16::from_be_bytes(machine),
+                _ => return Architecture::Unknown,
+            };
+            (*class, *data, machine)
+        }
+        _ => return Architecture::Unknown,
+    };
+    let is_64 = class == 2;
+    let is_le = data == 1;
+
+    match machine {
+        EM_386 => Architecture::X86,
+        EM_X86_64 => Architecture::X86_64,
+        EM_AARCH64 => Architecture::Aarch64,
+        EM_ARM if !is_64 && header.len() >= 40 => {
+            let flags = [header[36], header[37], header[38], header[39]];
+            let flags = if is_le {
+                u32::from_le_bytes(flags)
+            } else {
+                u32::from_be_bytes(flags)
+            };
+            if flags & EF_ARM_ABI_FLOAT_HARD != 0 {
+                Architecture::Armv7
+            } else {
+                Architecture::Arm
+            }
+        }
+        EM_ARM => Architecture::Arm,
+        EM_RISCV if is_64 => Architecture::Riscv64,
+        EM_RISCV => Architecture::Riscv32,
+        EM_PPC => Architecture::Ppc,
+        EM_PPC64 if is_le => Architecture::Ppc64le,
+        EM_PPC64 => Architecture::Ppc64,
+        EM_S390 if is_64 => Architecture::S390x,
+        EM_MIPS => match (is_64, is_le) {
+            (false, false) => Architecture::Mips,
+            (false, true) => Architecture::Mipsel,
+            (true, false) => Architecture::Mips64,
+            (true, true) => Architecture::Mips64el,
+        },
+        EM_LOONGARCH if is_64 => Architecture::Loongarch64,
+        EM_SPARC => Architecture::Sparc,
+        EM_SPARCV9 => Architecture::Sparc64,
+        _ => Architecture::Unknown,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn default() {
+        assert_eq!(Architecture::Unknown, Architecture::default());
+    }
+
+    #[test]
+    fn from_machine() {
+        let data = [
+            ("i686", Architecture::X86),
+            ("x86_64", Architecture::X86_64),
+            ("amd64\n", Architecture::X86_64),
+            ("aarch64", Architecture::Aarch64),
+            ("arm64", Architecture::Aarch64),
+            ("armv7l", Architecture::Armv7),
+            ("earmv7hf", Architecture::Armv7),
+            ("armv6l", Architecture::Armv6),
+            ("armel", Architecture::Arm),
+            ("riscv64", Architecture::Riscv64),
+            ("ppc64le", Architecture::Ppc64le),
+            ("ppc64", Architecture::Ppc64),
+            ("s390x", Architecture::S390x),
+            ("mips64el", Architecture::Mips64el),
+            ("loongarch64", Architecture::Loongarch64),
+            ("sparc64", Architecture::Sparc64),
+            ("", Architecture::Unknown),
+            ("vax", Architecture::Unknown),
+        ];
+
+        for (machine, expected) in &data {
+            assert_eq!(
+                Architecture::from_machine(machine),
+                *expected,
+                "{}",
+                machine
+            );
+        }
+    }
+
+    #[test]
+    fn package_names() {
+        let data = [
+            (Architecture::X86, Some("i386"), Some("i686")),
+            (Architecture::X86_64, Some("amd64"), Some("x86_64")),
+            (Architecture::Aarch64, Some("arm64"), Some("aarch64")),
+            (Architecture::Armv7, Some("armhf"), Some("armv7hl")),
+            (Architecture::Ppc64le, Some("ppc64el"), Some("ppc64le")),
+            (
+                Architecture::Loongarch64,
+                Some("loong64"),
+                Some("loongarch64"),
+            ),
+            (Architecture::Unknown, None, None),
+        ];
+
+        for (arch, debian, rpm) in &data {
+            assert_eq!(arch.debian_name(), *debian);
+            assert_eq!(arch.rpm_name(), *rpm);
+        }
+    }
+
+    #[test]
+    fn debian_names_round_trip() {
+        let data = [
+            Architecture::X86,
+            Architecture::X86_64,
+            Architecture::Aarch64,
+            Architecture::Armv7,
+            Architecture::Riscv64,
+            Architecture::Ppc64le,
+            Architecture::S390x,
+            Architecture::Loongarch64,
+        ];
+
+        for arch in &data {
+            let name = arch.debian_name().unwrap();
+            assert_eq!(Architecture::from_machine(name), *arch);
+            assert_eq!(Architecture::from_machine(&arch.to_string()), *arch);
+        }
+    }
+
+    #[test]
+    fn display() {
+        let data = [
+            (Architecture::Unknown, "unknown architecture"),
+            (Architecture::X86_64, "x86_64"),
+            (Architecture::Aarch64, "aarch64"),
+            (Architecture::Ppc64le, "ppc64le"),
+        ];
+
+        for (arch, expected) in &data {
+            assert_eq!(&arch.to_string(), expected);
+        }
+    }
+
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    #[test]
+    fn get_architecture() {
+        assert_ne!(get(), Architecture::Unknown);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn elf_machines() {
+        fn header(class: u8, data: u8, machine: u16) -> Vec<u8> {
+            let mut header = vec![0x7f, b'E', b'L', b'F', class, data];
+            header.resize(18, 0);
+            if data == 1 {
+                header.extend(&machine.to_le_bytes());
+            } else {
+                header.extend(&machine.to_be_bytes());
+            }
+            header
+        }
+
+        // A little-endian 32-bit ARM header with the given `e_flags`.
+        fn arm_header(flags: u32) -> Vec<u8> {
+            let mut header = header(1, 1, 40);
+            header.resize(36, 0);
+            header.extend(&flags.to_le_bytes());
+            header
+        }
+
+        let data = [
+            (header(2, 1, 62), Architecture::X86_64),
+            (header(1, 1, 3), Architecture::X86),
+            (header(2, 1, 183), Architecture::Aarch64),
+            (header(1, 1, 40), Architecture::Arm),
+            (arm_header(0x0500_0200), Architecture::Arm),
+            (arm_header(0x0500_0400), Architecture::Armv7),
+            (header(2, 1, 21), Architecture::Ppc64le),
+            (header(2, 2, 21), Architecture::Ppc64),
+            (header(2, 2, 22), Architecture::S390x),
+            (header(1, 2, 8), Architecture::Mips),
+            (header(2, 1, 8), Architecture::Mips64el),
+            (header(2, 1, 243), Architecture::Riscv64),
+            (header(2, 1, 258), Architecture::Loongarch64),
+            (header(2, 1, 0xffff), Architecture::Unknown),
+            (b"\x7fELF".to_vec(), Architecture::Unknown),
+        ];
+
+        for (header, expected) in &data {
+            assert_eq!(elf_machine(header), *expected);
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn from_current_exe() {
+        assert_ne!(from_elf(Path::new("/proc/self/exe")), Architecture::Unknown);
+    }
+}
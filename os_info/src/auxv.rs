@@ -0,0 +1,29 @@
+// spell-checker:ignore auxv, getauxval
+
+#![allow(unsafe_code)]
+
+use std::{ffi::CStr, os::raw::c_char};
+
+/// Returns the platform string (`AT_PLATFORM`) the kernel passed in the auxiliary vector of the
+/// current process, for example, `x86_64` or `i686`. Doesn't spawn a process or read any files.
+pub fn platform() -> Option<String> {
+    let value = unsafe { libc::getauxval(libc::AT_PLATFORM) };
+    if value == 0 {
+        return None;
+    }
+    // The value points to a NUL-terminated string that lives as long as the process.
+    let platform = unsafe { CStr::from_ptr(value as *const c_char) };
+    Some(platform.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_ne;
+
+    #[test]
+    fn platform_nonempty() {
+        let platform = platform().expect("AT_PLATFORM is missing");
+        assert_ne!(platform, "");
+    }
+}
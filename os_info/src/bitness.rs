@@ -1,6 +1,8 @@
-// spell-checker:ignore getconf
+// spell-checker:ignore getconf, sysctl
 
 use std::fmt::{self, Display, Formatter};
+#[cfg(target_os = "linux")]
+use std::{fs::File, io::Read, path::Path};
 #[cfg(any(
     target_os = "dragonfly",
     target_os = "freebsd",
@@ -9,10 +11,26 @@ use std::fmt::{self, Display, Formatter};
     target_os = "netbsd",
     target_os = "openbsd"
 ))]
-use std::process::{Command, Output};
+use std::{
+    process::{Command, Output},
+    time::Duration,
+};
+
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+use crate::{
+    sysctl::{sysctl, MACHINE_ARCH},
+    Architecture, Detector, Source,
+};
 
 /// Operating system architecture in terms of how many bits compose the basic values it can deal with.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[non_exhaustive]
 pub enum Bitness {
@@ -40,21 +58,51 @@ impl Display for Bitness {
     target_os = "linux",
     target_os = "macos",
 ))]
-pub fn get() -> Bitness {
-    match &Command::new("getconf").arg("LONG_BIT").output() {
+pub fn get(timeout: Option<Duration>) -> Bitness {
+    match &crate::command::output(Command::new("getconf").arg("LONG_BIT"), timeout) {
         Ok(Output { stdout, .. }) if stdout == b"32\n" => Bitness::X32,
         Ok(Output { stdout, .. }) if stdout == b"64\n" => Bitness::X64,
         _ => Bitness::Unknown,
     }
 }
 
+/// Determines bitness from the class of the ELF executable at the given path.
+#[cfg(target_os = "linux")]
+pub fn from_elf(path: &Path) -> Bitness {
+    let mut header = [0; 5];
+    match File::open(path).and_then(|mut file| file.read_exact(&mut header)) {
+        Ok(()) => elf_class(&header),
+        Err(e) => {
+            log::trace!("Unable to read ELF header of {:?}: {:?}", path, e);
+            Bitness::Unknown
+        }
+    }
+}
+
+/// Determines bitness from the platform the kernel reported in the auxiliary vector of the current
+/// process.
+#[cfg(target_os = "linux")]
+pub fn from_auxv() -> Bitness {
+    crate::auxv::platform()
+        .map(|platform| crate::Architecture::from_machine(&platform).bitness())
+        .unwrap_or(Bitness::Unknown)
+}
+
+#[cfg(target_os = "linux")]
+fn elf_class(header: &[u8]) -> Bitness {
+    match header {
+        [0x7f, b'E', b'L', b'F', 1, ..] => Bitness::X32,
+        [0x7f, b'E', b'L', b'F', 2, ..] => Bitness::X64,
+        _ => Bitness::Unknown,
+    }
+}
+
 #[cfg(target_os = "netbsd")]
-pub fn get() -> Bitness {
-    match &Command::new("sysctl")
-        .arg("-n")
-        .arg("hw.machine_arch")
-        .output()
-    {
+pub fn get(timeout: Option<Duration>) -> Bitness {
+    match &crate::command::output(
+        Command::new("sysctl").arg("-n").arg("hw.machine_arch"),
+        timeout,
+    ) {
         Ok(Output { stdout, .. }) if stdout == b"amd64\n" => Bitness::X64,
         Ok(Output { stdout, .. }) if stdout == b"x86_64\n" => Bitness::X64,
         Ok(Output { stdout, .. }) if stdout == b"i386\n" => Bitness::X32,
@@ -66,8 +114,8 @@ pub fn get() -> Bitness {
 }
 
 #[cfg(target_os = "openbsd")]
-pub fn get() -> Bitness {
-    match &Command::new("sysctl").arg("-n").arg("hw.machine").output() {
+pub fn get(timeout: Option<Duration>) -> Bitness {
+    match &crate::command::output(Command::new("sysctl").arg("-n").arg("hw.machine"), timeout) {
         Ok(Output { stdout, .. }) if stdout == b"amd64\n" => Bitness::X64,
         Ok(Output { stdout, .. }) if stdout == b"x86_64\n" => Bitness::X64,
         Ok(Output { stdout, .. }) if stdout == b"i386\n" => Bitness::X32,
@@ -78,6 +126,41 @@ pub fn get() -> Bitness {
     }
 }
 
+/// Determines bitness using the first of `Source::Getconf` and `Source::Sysctl` enabled in the
+/// detector, or from the architecture if neither is.
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+pub fn detect(detector: &Detector, architecture: Architecture) -> Bitness {
+    detector
+        .sources
+        .iter()
+        .find_map(|source| match source {
+            Source::Getconf => Some(get(detector.timeout)),
+            Source::Sysctl => Some(from_sysctl()),
+            _ => None,
+        })
+        .unwrap_or_else(|| architecture.bitness())
+}
+
+/// Determines bitness from the architecture reported by the `sysctl` system call.
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+fn from_sysctl() -> Bitness {
+    sysctl(MACHINE_ARCH)
+        .map(|arch| Architecture::from_machine(&arch).bitness())
+        .unwrap_or(Bitness::Unknown)
+}
+
 #[cfg(all(
     test,
     any(
@@ -93,9 +176,10 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_ne;
 
+    #[cfg(not(feature = "no-subprocess"))]
     #[test]
     fn get_bitness() {
-        let b = get();
+        let b = get(None);
         assert_ne!(b, Bitness::Unknown);
     }
 
@@ -111,4 +195,34 @@ mod tests {
             assert_eq!(&bitness.to_string(), expected);
         }
     }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn from_current_exe() {
+        let b = from_elf(Path::new("/proc/self/exe"));
+        assert_ne!(b, Bitness::Unknown);
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn from_current_process() {
+        assert_eq!(from_auxv(), from_elf(Path::new("/proc/self/exe")));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn elf_classes() {
+        let data = [
+            (b"".as_ref(), Bitness::Unknown),
+            (b"\x7fELF", Bitness::Unknown),
+            (b"\x7fELF\x01\x01\x01", Bitness::X32),
+            (b"\x7fELF\x02\x01\x01", Bitness::X64),
+            (b"\x7fELF\x03", Bitness::Unknown),
+            (b"#!/bin/sh", Bitness::Unknown),
+        ];
+
+        for (header, expected) in &data {
+            assert_eq!(elf_class(header), *expected);
+        }
+    }
 }
@@ -0,0 +1,328 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use super::{Architecture, Bitness, Info, KernelInfo, OsRelease, Type, Version};
+
+/// Assembles `Info` from values obtained elsewhere, for example, from an inventory database or in
+/// test fixtures.
+///
+/// All fields that aren't set are unknown, and the provenance of the result is empty.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::{Bitness, InfoBuilder, Type, Version};
+///
+/// let info = InfoBuilder::new(Type::Ubuntu)
+///     .version(Version::Semantic(22, 4, 0))
+///     .edition("Server")
+///     .codename("jammy")
+///     .bitness(Bitness::X64)
+///     .build()
+///     .unwrap();
+/// assert_eq!(Some("jammy"), info.codename());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InfoBuilder {
+    info: Info,
+}
+
+impl InfoBuilder {
+    /// Constructs a builder of `Info` with the given operating system type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{InfoBuilder, Type};
+    ///
+    /// let info = InfoBuilder::new(Type::Fedora).build().unwrap();
+    /// assert_eq!(Type::Fedora, info.os_type());
+    /// ```
+    pub fn new(os_type: Type) -> Self {
+        Self {
+            info: Info::with_type(os_type),
+        }
+    }
+
+    /// Sets the version.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{InfoBuilder, Type, Version};
+    ///
+    /// let info = InfoBuilder::new(Type::Debian)
+    ///     .version(Version::from_string("12"))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(Some(12), info.version().major());
+    /// ```
+    pub fn version(mut self, version: Version) -> Self {
+        self.info.version = version;
+        self
+    }
+
+    /// Sets the edition.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{InfoBuilder, Type};
+    ///
+    /// let info = InfoBuilder::new(Type::Windows).edition("Windows 10 Pro").build().unwrap();
+    /// assert_eq!(Some("Windows 10 Pro"), info.edition());
+    /// ```
+    pub fn edition<S: Into<String>>(mut self, edition: S) -> Self {
+        self.info.edition = Some(edition.into());
+        self
+    }
+
+    /// Sets the codename.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{InfoBuilder, Type};
+    ///
+    /// let info = InfoBuilder::new(Type::Debian).codename("bookworm").build().unwrap();
+    /// assert_eq!(Some("bookworm"), info.codename());
+    /// ```
+    pub fn codename<S: Into<String>>(mut self, codename: S) -> Self {
+        self.info.codename = Some(codename.into());
+        self
+    }
+
+    /// Sets the bitness.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Bitness, InfoBuilder, Type};
+    ///
+    /// let info = InfoBuilder::new(Type::Linux).bitness(Bitness::X64).build().unwrap();
+    /// assert_eq!(Bitness::X64, info.bitness());
+    /// ```
+    pub fn bitness(mut self, bitness: Bitness) -> Self {
+        self.info.bitness = bitness;
+        self
+    }
+
+    /// Sets the processor architecture.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Architecture, InfoBuilder, Type};
+    ///
+    /// let info = InfoBuilder::new(Type::Linux)
+    ///     .architecture(Architecture::Aarch64)
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(Architecture::Aarch64, info.architecture());
+    /// ```
+    pub fn architecture(mut self, architecture: Architecture) -> Self {
+        self.info.architecture = architecture;
+        self
+    }
+
+    /// Sets the kernel information.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{InfoBuilder, KernelInfo, Type};
+    ///
+    /// let kernel = KernelInfo::new("Linux", "5.15.0-91-generic", "#101-Ubuntu SMP", "x86_64");
+    /// let info = InfoBuilder::new(Type::Ubuntu).kernel(kernel).build().unwrap();
+    /// assert_eq!(Some("Linux"), info.kernel().map(|k| k.sysname()));
+    /// ```
+    pub fn kernel(mut self, kernel: KernelInfo) -> Self {
+        self.info.kernel = Some(kernel);
+        self
+    }
+
+    /// Sets the contents of the `os-release` file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{InfoBuilder, OsRelease, Type};
+    ///
+    /// let info = InfoBuilder::new(Type::Ubuntu)
+    ///     .os_release(OsRelease::parse("ID=ubuntu\nVERSION_ID=\"22.04\""))
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(Some("ubuntu"), info.os_release().and_then(|r| r.id()));
+    /// ```
+    pub fn os_release(mut self, os_release: OsRelease) -> Self {
+        self.info.os_release = Some(os_release);
+        self
+    }
+
+    /// Sets the distribution and release the operating system is based on. See `Info::upstream`
+    /// for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Info, InfoBuilder, Type, Version};
+    ///
+    /// let ubuntu = InfoBuilder::new(Type::Ubuntu)
+    ///     .version(Version::Semantic(22, 4, 0))
+    ///     .build()
+    ///     .unwrap();
+    /// let info = InfoBuilder::new(Type::Pop).upstream(ubuntu).build().unwrap();
+    /// assert_eq!(Some(Type::Ubuntu), info.upstream().map(|u| u.os_type()));
+    /// ```
+    pub fn upstream(mut self, upstream: Info) -> Self {
+        self.info.upstream = Some(Box::new(upstream));
+        self
+    }
+
+    /// Validates the values and returns the assembled `Info`.
+    ///
+    /// Text values must not be blank, and the bitness must not exceed the bitness of the
+    /// architecture (a 32-bit system can run on a 64-bit processor, but not vice versa).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Architecture, Bitness, InfoBuilder, Type};
+    ///
+    /// let result = InfoBuilder::new(Type::Linux)
+    ///     .architecture(Architecture::X86)
+    ///     .bitness(Bitness::X64)
+    ///     .build();
+    /// assert!(result.is_err());
+    /// ```
+    pub fn build(self) -> Result<Info, InfoBuildError> {
+        let info = self.info;
+        let blank = |value: Option<&str>| value.is_some_and(|v| v.trim().is_empty());
+        let kernel = info.kernel.as_ref();
+        let fields = [
+            (
+                "version",
+                matches!(&info.version, Version::Custom(v) if v.trim().is_empty()),
+            ),
+            ("edition", blank(info.edition.as_deref())),
+            ("codename", blank(info.codename.as_deref())),
+            ("kernel sysname", blank(kernel.map(|k| k.sysname()))),
+            ("kernel release", blank(kernel.map(|k| k.release()))),
+        ];
+        if let Some((field, _)) = fields.iter().find(|(_, blank)| *blank) {
+            return Err(InfoBuildError::BlankField { field });
+        }
+
+        if info.bitness == Bitness::X64 && info.architecture.bitness() == Bitness::X32 {
+            return Err(InfoBuildError::InconsistentBitness {
+                bitness: info.bitness,
+                architecture: info.architecture,
+            });
+        }
+        Ok(info)
+    }
+}
+
+/// An error which can be returned by `InfoBuilder::build`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum InfoBuildError {
+    /// A text value is empty or consists of whitespace only.
+    BlankField {
+        /// Name of the field.
+        field: &'static str,
+    },
+    /// The bitness is impossible for the architecture.
+    InconsistentBitness {
+        /// The bitness of the operating system.
+        bitness: Bitness,
+        /// The processor architecture.
+        architecture: Architecture,
+    },
+}
+
+impl Display for InfoBuildError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            InfoBuildError::BlankField { field } => write!(f, "{} is blank", field),
+            InfoBuildError::InconsistentBitness {
+                bitness,
+                architecture,
+            } => write!(f, "{} system can't run on {}", bitness, architecture),
+        }
+    }
+}
+
+impl Error for InfoBuildError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn build() {
+        let info = InfoBuilder::new(Type::Ubuntu)
+            .version(Version::Semantic(22, 4, 0))
+            .edition("Server")
+            .codename("jammy")
+            .bitness(Bitness::X32)
+            .architecture(Architecture::X86_64)
+            .kernel(KernelInfo::new("Linux", "5.15.0", "", "x86_64"))
+            .os_release(OsRelease::parse("ID=ubuntu"))
+            .build()
+            .unwrap();
+
+        let expected = Info {
+            os_type: Type::Ubuntu,
+            version: Version::Semantic(22, 4, 0),
+            edition: Some("Server".to_owned()),
+            codename: Some("jammy".to_owned()),
+            bitness: Bitness::X32,
+            architecture: Architecture::X86_64,
+            kernel: Some(KernelInfo::new("Linux", "5.15.0", "", "x86_64")),
+            os_release: Some(OsRelease::parse("ID=ubuntu")),
+            ..Default::default()
+        };
+        assert_eq!(info, expected);
+        assert_eq!(
+            InfoBuilder::new(Type::Linux).build(),
+            Ok(Info::with_type(Type::Linux))
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let data = [
+            (
+                InfoBuilder::new(Type::Linux).version(Version::Custom(" ".to_owned())),
+                "version is blank",
+            ),
+            (
+                InfoBuilder::new(Type::Linux).edition(""),
+                "edition is blank",
+            ),
+            (
+                InfoBuilder::new(Type::Linux).codename("\t"),
+                "codename is blank",
+            ),
+            (
+                InfoBuilder::new(Type::Linux).kernel(KernelInfo::new("", "5.15.0", "", "")),
+                "kernel sysname is blank",
+            ),
+            (
+                InfoBuilder::new(Type::Linux)
+                    .bitness(Bitness::X64)
+                    .architecture(Architecture::Armv7),
+                "64-bit system can't run on armv7",
+            ),
+        ];
+
+        for (builder, expected) in &data {
+            let error = builder.clone().build().unwrap_err();
+            assert_eq!(error.to_string(), *expected);
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use log::trace;
+
+use crate::{testing, Info};
+
+/// Information about the current operating system, detected on the first use.
+static CACHE: Mutex<Option<Arc<Info>>> = Mutex::new(None);
+
+/// Returns information about the current operating system, detecting it only once per process.
+///
+/// The first call performs the detection (see `get`), and all subsequent calls from any thread
+/// return the same value without reading files or running commands. Use `refresh` to detect the
+/// operating system again, for example, after an in-place upgrade.
+///
+/// # Examples
+///
+/// ```
+/// let info = os_info::get_cached();
+/// println!("OS information: {}", info);
+/// assert_eq!(*info, *os_info::get_cached());
+/// ```
+pub fn get_cached() -> Arc<Info> {
+    // An override is neither cached nor hidden by the cached value.
+    if let Some(info) = testing::overridden() {
+        return Arc::new(info);
+    }
+
+    let mut cache = lock();
+    match &*cache {
+        Some(info) => Arc::clone(info),
+        None => {
+            trace!("Detecting the operating system for the cache");
+            let info = Arc::new(crate::get());
+            *cache = Some(Arc::clone(&info));
+            info
+        }
+    }
+}
+
+/// Detects the operating system again, replaces the value returned by `get_cached` and returns it.
+/// Values obtained before the refresh are unaffected.
+///
+/// # Examples
+///
+/// ```
+/// let info = os_info::refresh();
+/// assert_eq!(*info, *os_info::get_cached());
+/// ```
+pub fn refresh() -> Arc<Info> {
+    let info = Arc::new(crate::get());
+    *lock() = Some(Arc::clone(&info));
+    info
+}
+
+fn lock() -> MutexGuard<'static, Option<Arc<Info>>> {
+    // A panic during the detection leaves the cache empty, so a poisoned value is still valid.
+    CACHE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::thread;
+
+    #[test]
+    fn cached() {
+        let info = get_cached();
+        assert_eq!(*info, crate::get());
+        assert_eq!(*info, *get_cached());
+    }
+
+    #[test]
+    fn threads() {
+        let handles: Vec<_> = (0..4).map(|_| thread::spawn(get_cached)).collect();
+        let infos: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        for info in &infos {
+            assert_eq!(**info, *infos[0]);
+        }
+    }
+
+    #[test]
+    fn refreshed() {
+        let info = refresh();
+        assert_eq!(*info, crate::get());
+        // Another test may refresh the cache concurrently, so only the value is compared.
+        assert_eq!(*get_cached(), *info);
+    }
+}
@@ -0,0 +1,214 @@
+// spell-checker:ignore bionic, bookworm, bullseye, buster, capitan, cosmic, eoan, focal, forky,
+// spell-checker:ignore froyo, heisenbug, hirsute, impish, jessie, kitkat, lovelock, mantic,
+// spell-checker:ignore mavericks, mojave, oracular, plucky, questing, sequoia, stretch, trixie,
+// spell-checker:ignore xenial
+
+use super::{Type, Version};
+
+/// Codenames of operating system versions, in the order of release within every type.
+///
+/// Ubuntu and Debian codenames are written the way `lsb_release` and `VERSION_CODENAME` report
+/// them. Fedora stopped using codenames after version 20.
+const CODENAMES: &[(Type, &str, &str)] = &[
+    (Type::Android, "1.5", "Cupcake"),
+    (Type::Android, "1.6", "Donut"),
+    (Type::Android, "2.0", "Eclair"),
+    (Type::Android, "2.1", "Eclair"),
+    (Type::Android, "2.2", "Froyo"),
+    (Type::Android, "2.3", "Gingerbread"),
+    (Type::Android, "3", "Honeycomb"),
+    (Type::Android, "4.0", "Ice Cream Sandwich"),
+    (Type::Android, "4.1", "Jelly Bean"),
+    (Type::Android, "4.2", "Jelly Bean"),
+    (Type::Android, "4.3", "Jelly Bean"),
+    (Type::Android, "4.4", "KitKat"),
+    (Type::Android, "5", "Lollipop"),
+    (Type::Android, "6", "Marshmallow"),
+    (Type::Android, "7", "Nougat"),
+    (Type::Android, "8", "Oreo"),
+    (Type::Android, "9", "Pie"),
+    (Type::Android, "10", "Quince Tart"),
+    (Type::Android, "11", "Red Velvet Cake"),
+    (Type::Android, "12", "Snow Cone"),
+    (Type::Android, "13", "Tiramisu"),
+    (Type::Android, "14", "Upside Down Cake"),
+    (Type::Android, "15", "Vanilla Ice Cream"),
+    (Type::Android, "16", "Baklava"),
+    (Type::Debian, "7", "wheezy"),
+    (Type::Debian, "8", "jessie"),
+    (Type::Debian, "9", "stretch"),
+    (Type::Debian, "10", "buster"),
+    (Type::Debian, "11", "bullseye"),
+    (Type::Debian, "12", "bookworm"),
+    (Type::Debian, "13", "trixie"),
+    (Type::Debian, "14", "forky"),
+    (Type::Fedora, "15", "Lovelock"),
+    (Type::Fedora, "16", "Verne"),
+    (Type::Fedora, "17", "Beefy Miracle"),
+    (Type::Fedora, "18", "Spherical Cow"),
+    (Type::Fedora, "19", "Schrödinger's Cat"),
+    (Type::Fedora, "20", "Heisenbug"),
+    (Type::Macos, "10.0", "Cheetah"),
+    (Type::Macos, "10.1", "Puma"),
+    (Type::Macos, "10.2", "Jaguar"),
+    (Type::Macos, "10.3", "Panther"),
+    (Type::Macos, "10.4", "Tiger"),
+    (Type::Macos, "10.5", "Leopard"),
+    (Type::Macos, "10.6", "Snow Leopard"),
+    (Type::Macos, "10.7", "Lion"),
+    (Type::Macos, "10.8", "Mountain Lion"),
+    (Type::Macos, "10.9", "Mavericks"),
+    (Type::Macos, "10.10", "Yosemite"),
+    (Type::Macos, "10.11", "El Capitan"),
+    (Type::Macos, "10.12", "Sierra"),
+    (Type::Macos, "10.13", "High Sierra"),
+    (Type::Macos, "10.14", "Mojave"),
+    (Type::Macos, "10.15", "Catalina"),
+    (Type::Macos, "11", "Big Sur"),
+    (Type::Macos, "12", "Monterey"),
+    (Type::Macos, "13", "Ventura"),
+    (Type::Macos, "14", "Sonoma"),
+    (Type::Macos, "15", "Sequoia"),
+    (Type::Macos, "26", "Tahoe"),
+    (Type::Ubuntu, "14.04", "trusty"),
+    (Type::Ubuntu, "16.04", "xenial"),
+    (Type::Ubuntu, "18.04", "bionic"),
+    (Type::Ubuntu, "18.10", "cosmic"),
+    (Type::Ubuntu, "19.04", "disco"),
+    (Type::Ubuntu, "19.10", "eoan"),
+    (Type::Ubuntu, "20.04", "focal"),
+    (Type::Ubuntu, "20.10", "groovy"),
+    (Type::Ubuntu, "21.04", "hirsute"),
+    (Type::Ubuntu, "21.10", "impish"),
+    (Type::Ubuntu, "22.04", "jammy"),
+    (Type::Ubuntu, "22.10", "kinetic"),
+    (Type::Ubuntu, "23.04", "lunar"),
+    (Type::Ubuntu, "23.10", "mantic"),
+    (Type::Ubuntu, "24.04", "noble"),
+    (Type::Ubuntu, "24.10", "oracular"),
+    (Type::Ubuntu, "25.04", "plucky"),
+    (Type::Ubuntu, "25.10", "questing"),
+];
+
+/// Returns the codename of the given operating system version, if it's known.
+///
+/// Android dessert names, macOS marketing names and the codenames of Debian, Ubuntu and older
+/// Fedora versions are known. `Info::codename` falls back to this table if the operating system
+/// doesn't report a codename.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::{Type, Version};
+///
+/// assert_eq!(Some("bookworm"), os_info::lookup_codename(Type::Debian, &Version::Semantic(12, 4, 0)));
+/// assert_eq!(Some("Monterey"), os_info::lookup_codename(Type::Macos, &Version::Semantic(12, 7, 1)));
+/// assert_eq!(None, os_info::lookup_codename(Type::Windows, &Version::Semantic(10, 0, 19045)));
+/// ```
+pub fn lookup_codename(os_type: Type, version: &Version) -> Option<&'static str> {
+    let components = version.components();
+    CODENAMES
+        .iter()
+        .filter(|(t, _, _)| *t == os_type)
+        .map(|(_, v, codename)| (Version::from_string_exact(v).components(), codename))
+        .filter(|(v, _)| !v.is_empty() && components.starts_with(v))
+        .max_by_key(|(v, _)| v.len())
+        .map(|(_, codename)| *codename)
+}
+
+/// Returns the operating system type and version with the given codename. The comparison is
+/// case-insensitive, and the earliest version is returned if the codename was used for several.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::{Type, Version};
+///
+/// assert_eq!(Some((Type::Debian, Version::Semantic(12, 0, 0))), os_info::lookup_release("bookworm"));
+/// assert_eq!(Some((Type::Macos, Version::Semantic(10, 15, 0))), os_info::lookup_release("Catalina"));
+/// assert_eq!(None, os_info::lookup_release("unknown"));
+/// ```
+pub fn lookup_release(codename: &str) -> Option<(Type, Version)> {
+    let codename = codename.trim();
+    CODENAMES
+        .iter()
+        .find(|(_, _, c)| c.eq_ignore_ascii_case(codename))
+        .map(|(os_type, version, _)| (*os_type, Version::from_string(*version)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn codenames() {
+        let data = [
+            (Type::Ubuntu, Version::Semantic(22, 4, 0), Some("jammy")),
+            (
+                Type::Ubuntu,
+                Version::Custom("18.10".to_owned()),
+                Some("cosmic"),
+            ),
+            (Type::Ubuntu, Version::Semantic(22, 5, 0), None),
+            (Type::Debian, Version::Semantic(11, 0, 0), Some("bullseye")),
+            (Type::Fedora, Version::Semantic(20, 0, 0), Some("Heisenbug")),
+            (Type::Fedora, Version::Semantic(35, 0, 0), None),
+            (Type::Macos, Version::Semantic(10, 1, 5), Some("Puma")),
+            (Type::Macos, Version::Semantic(10, 10, 5), Some("Yosemite")),
+            (Type::Macos, Version::Semantic(14, 2, 1), Some("Sonoma")),
+            (Type::Android, Version::Semantic(4, 4, 2), Some("KitKat")),
+            (Type::Android, Version::Semantic(3, 2, 0), Some("Honeycomb")),
+            (Type::Android, Version::Unknown, None),
+            (Type::Arch, Version::Rolling(None), None),
+        ];
+
+        for (os_type, version, expected) in &data {
+            assert_eq!(
+                lookup_codename(*os_type, version),
+                *expected,
+                "{} {}",
+                os_type,
+                version
+            );
+        }
+    }
+
+    #[test]
+    fn releases() {
+        let data = [
+            ("jammy", Some((Type::Ubuntu, Version::Semantic(22, 4, 0)))),
+            (
+                "Bookworm",
+                Some((Type::Debian, Version::Semantic(12, 0, 0))),
+            ),
+            (
+                "el capitan",
+                Some((Type::Macos, Version::Semantic(10, 11, 0))),
+            ),
+            (
+                "Jelly Bean",
+                Some((Type::Android, Version::Semantic(4, 1, 0))),
+            ),
+            ("sid", None),
+        ];
+
+        for (codename, expected) in &data {
+            assert_eq!(lookup_release(codename), *expected, "{}", codename);
+        }
+    }
+
+    #[test]
+    fn round_trip() {
+        for (os_type, version, codename) in CODENAMES {
+            let (release_type, release) = lookup_release(codename).unwrap();
+            assert_eq!(release_type, *os_type);
+            assert_eq!(
+                lookup_codename(*os_type, &release),
+                Some(*codename),
+                "{}",
+                version
+            );
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::{
+    io::{self, Read},
+    process::{Command, Output, Stdio},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How often a running command is checked for completion when a timeout is set.
+const POLL_INTERVAL: Duration = Duration::from_millis(5);
+
+/// Runs the command and collects its output. If a timeout is given and the command doesn't finish
+/// in time, it is killed and an error of the `TimedOut` kind is returned. If the `no-subprocess`
+/// feature is enabled, the command isn't run and an error of the `Unsupported` kind is returned.
+pub fn output(command: &mut Command, timeout: Option<Duration>) -> io::Result<Output> {
+    if cfg!(feature = "no-subprocess") {
+        return Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "spawning processes is disabled by the `no-subprocess` feature",
+        ));
+    }
+
+    let timeout = match timeout {
+        Some(timeout) => timeout,
+        None => return command.output(),
+    };
+
+    let mut child = command
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    // Read the output concurrently, so the command doesn't block on a full pipe.
+    let reader = child.stdout.take().map(|mut stdout| {
+        thread::spawn(move || {
+            let mut buffer = Vec::new();
+            stdout.read_to_end(&mut buffer).map(|_| buffer)
+        })
+    });
+
+    let start = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if start.elapsed() >= timeout {
+            // The command may have finished in the meantime, so a failure to kill it is ignored.
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("timed out after {:?}", timeout),
+            ));
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+
+    let stdout = match reader {
+        Some(reader) => reader
+            .join()
+            .map_err(|_| io::Error::other("unable to read the output"))??,
+        None => Vec::new(),
+    };
+
+    Ok(Output {
+        status,
+        stdout,
+        stderr: Vec::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[cfg(not(feature = "no-subprocess"))]
+    #[test]
+    fn without_timeout() {
+        let output = output(Command::new("echo").arg("hello"), None).unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hello\n");
+    }
+
+    #[cfg(not(feature = "no-subprocess"))]
+    #[test]
+    fn within_timeout() {
+        let output = output(
+            Command::new("echo").arg("hello"),
+            Some(Duration::from_secs(10)),
+        )
+        .unwrap();
+        assert!(output.status.success());
+        assert_eq!(output.stdout, b"hello\n");
+    }
+
+    #[cfg(not(feature = "no-subprocess"))]
+    #[test]
+    fn timed_out() {
+        let start = Instant::now();
+        let error = output(
+            Command::new("sleep").arg("10"),
+            Some(Duration::from_millis(50)),
+        )
+        .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::TimedOut);
+        assert!(start.elapsed() < Duration::from_secs(5));
+    }
+
+    #[cfg(not(feature = "no-subprocess"))]
+    #[test]
+    fn missing_command() {
+        let error = output(
+            &mut Command::new("os_info_missing_command"),
+            Some(Duration::from_secs(1)),
+        )
+        .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::NotFound);
+    }
+
+    #[cfg(feature = "no-subprocess")]
+    #[test]
+    fn disabled() {
+        let error = output(&mut Command::new("true"), None).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::Unsupported);
+    }
+}
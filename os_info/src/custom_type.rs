@@ -0,0 +1,160 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::Family;
+
+/// An operating system that doesn't have its own `Type` variant, for example, an in-house Linux
+/// distribution. Custom types are used through `Type::Other` and are usually registered with
+/// `Distribution`.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::{CustomType, Family, Type};
+///
+/// const ACME: CustomType = CustomType::new("acme", "Acme Linux", Family::RedHat);
+///
+/// assert_eq!("Acme Linux", Type::Other(ACME).to_string());
+/// assert_eq!(Family::RedHat, Type::Other(ACME).family());
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CustomType {
+    id: &'static str,
+    name: &'static str,
+    #[cfg_attr(feature = "schemars", schemars(default))]
+    family: Family,
+}
+
+impl CustomType {
+    /// Constructs a custom type with the given lower-case identifier (preferably the `ID` field of
+    /// the `os-release` file), display name and family.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{CustomType, Family};
+    ///
+    /// let acme = CustomType::new("acme", "Acme Linux", Family::RedHat);
+    /// assert_eq!("acme", acme.id());
+    /// ```
+    pub const fn new(id: &'static str, name: &'static str, family: Family) -> Self {
+        Self { id, name, family }
+    }
+
+    /// Constructs a custom type from strings known only at runtime, for example, read from a
+    /// configuration file. Each distinct string is kept for the rest of the process, so this is
+    /// meant for a bounded set of operating systems.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{CustomType, Family};
+    ///
+    /// let id = String::from("acme");
+    /// let acme = CustomType::from_owned(id, "Acme Linux".to_owned(), Family::RedHat);
+    /// assert_eq!(CustomType::new("acme", "Acme Linux", Family::RedHat), acme);
+    /// ```
+    pub fn from_owned<I, N>(id: I, name: N, family: Family) -> Self
+    where
+        I: Into<String>,
+        N: Into<String>,
+    {
+        Self {
+            id: crate::registry::intern(id.into()),
+            name: crate::registry::intern(name.into()),
+            family,
+        }
+    }
+
+    /// Returns the identifier of the operating system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{CustomType, Family};
+    ///
+    /// let acme = CustomType::new("acme", "Acme Linux", Family::RedHat);
+    /// assert_eq!("acme", acme.id());
+    /// ```
+    pub fn id(&self) -> &'static str {
+        self.id
+    }
+
+    /// Returns the display name of the operating system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{CustomType, Family};
+    ///
+    /// let acme = CustomType::new("acme", "Acme Linux", Family::RedHat);
+    /// assert_eq!("Acme Linux", acme.name());
+    /// ```
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the family of the operating system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{CustomType, Family};
+    ///
+    /// let acme = CustomType::new("acme", "Acme Linux", Family::RedHat);
+    /// assert_eq!(Family::RedHat, acme.family());
+    /// ```
+    pub fn family(&self) -> Family {
+        self.family
+    }
+}
+
+impl Display for CustomType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+/// Only custom types registered in this process can be deserialized, as the strings of a custom
+/// type must live for the rest of the process. Unregistered identifiers are rejected here, while
+/// `Type` reads them as `Type::Unknown`.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for CustomType {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(serde::Deserialize)]
+        struct Fields {
+            id: String,
+        }
+
+        let fields = Fields::deserialize(deserializer)?;
+        crate::registry::custom_type(&fields.id).ok_or_else(|| {
+            serde::de::Error::custom(format!("unregistered custom type `{}`", fields.id))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn display() {
+        let custom = CustomType::new("acme", "Acme Linux", Family::RedHat);
+        assert_eq!(custom.to_string(), "Acme Linux");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        const CUSTOM: CustomType = CustomType::new("custom-serde", "Custom Serde", Family::Arch);
+        crate::Distribution::new(crate::Type::Other(CUSTOM)).register();
+
+        let json = serde_json::to_string(&CUSTOM).unwrap();
+        assert_eq!(serde_json::from_str::<CustomType>(&json).unwrap(), CUSTOM);
+
+        let json = r#"{"id":"custom-unregistered","name":"Unregistered","family":"Arch"}"#;
+        assert!(serde_json::from_str::<CustomType>(json).is_err());
+    }
+}
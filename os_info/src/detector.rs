@@ -0,0 +1,334 @@
+// spell-checker:ignore getconf
+
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+#[cfg(feature = "async")]
+use std::future::Future;
+
+#[cfg(feature = "async")]
+use super::future::Detection;
+use super::{imp, lookup_codename, testing, DetectionError, Info, Source};
+
+/// Sources consulted by default, in the order of precedence.
+const DEFAULT_SOURCES: [Source; 5] = [
+    Source::LsbRelease,
+    Source::ReleaseFile,
+    Source::OsRelease,
+    Source::Command,
+    Source::Getconf,
+];
+
+/// A configurable operating system detector.
+///
+/// `os_info::get()` is a shorthand for `Detector::default().detect()`. The detector allows
+/// choosing which sources are consulted and in what order, detecting the operating system installed
+/// under another root directory and limiting the time external commands can take.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use os_info::{Detector, Source};
+///
+/// let info = Detector::new()
+///     .sources(vec![Source::OsRelease, Source::ReleaseFile])
+///     .timeout(Duration::from_secs(1))
+///     .detect();
+/// println!("OS information: {}", info);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Detector {
+    pub(crate) sources: Vec<Source>,
+    pub(crate) root: Option<PathBuf>,
+    pub(crate) timeout: Option<Duration>,
+}
+
+impl Detector {
+    /// Constructs a detector with the default configuration: all sources are consulted in the
+    /// default order, the current system is detected and commands have no time limit. If the
+    /// `no-subprocess` feature is enabled, the detector is subprocess-free (see
+    /// `Detector::subprocess_free`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Detector;
+    ///
+    /// assert_eq!(Detector::default(), Detector::new());
+    /// ```
+    pub fn new() -> Self {
+        let detector = Self {
+            sources: DEFAULT_SOURCES.to_vec(),
+            root: None,
+            timeout: None,
+        };
+        if cfg!(feature = "no-subprocess") {
+            detector.subprocess_free()
+        } else {
+            detector
+        }
+    }
+
+    /// Sets the sources to consult, in the order of precedence. Sources that aren't listed aren't
+    /// consulted at all.
+    ///
+    /// On Linux, `Source::LsbRelease` is the `lsb_release` command, or the `/etc/lsb-release`
+    /// file when a root directory is set. `Source::LsbReleaseFile` gives the same result as the
+    /// command without running it. Release files of `Source::ReleaseFile` are checked in their
+    /// built-in order, for example, `/etc/oracle-release` before `/etc/redhat-release`, because
+    /// Oracle Linux ships the release file of Red Hat Enterprise Linux. Without `Source::Getconf`
+    /// the bitness is determined without running `getconf`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Detector, Source};
+    ///
+    /// // Only read the `os-release` file.
+    /// let info = Detector::new().sources(vec![Source::OsRelease]).detect();
+    /// println!("OS information: {}", info);
+    /// ```
+    pub fn sources<I: IntoIterator<Item = Source>>(mut self, sources: I) -> Self {
+        self.sources = Vec::new();
+        for source in sources {
+            if !self.sources.contains(&source) {
+                self.sources.push(source);
+            }
+        }
+        self
+    }
+
+    /// Disables the given source, keeping the order of the remaining ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Detector, Source};
+    ///
+    /// let info = Detector::new()
+    ///     .without(Source::LsbRelease)
+    ///     .without(Source::Getconf)
+    ///     .detect();
+    /// println!("OS information: {}", info);
+    /// ```
+    pub fn without(mut self, source: Source) -> Self {
+        self.sources.retain(|&s| s != source);
+        self
+    }
+
+    /// Replaces the sources that spawn processes with equivalents that only read files and make
+    /// system calls: `Source::LsbRelease` with `Source::LsbReleaseFile`, `Source::Command` and
+    /// `Source::Getconf` with `Source::Sysctl`. The bitness is then determined from ELF headers and
+    /// the auxiliary vector on Linux and from `sysctl` on BSD systems and macOS.
+    ///
+    /// Enable the `no-subprocess` feature to make this the default and to prevent spawning
+    /// processes even if the command sources are requested explicitly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Detector, Source};
+    ///
+    /// let detector = Detector::new().subprocess_free();
+    /// assert!(!detector.uses(Source::LsbRelease));
+    /// println!("OS information: {}", detector.detect());
+    /// ```
+    pub fn subprocess_free(self) -> Self {
+        let sources: Vec<_> = self
+            .sources
+            .iter()
+            .map(|&source| match source {
+                Source::LsbRelease => Source::LsbReleaseFile,
+                Source::Command | Source::Getconf => Source::Sysctl,
+                source => source,
+            })
+            .collect();
+        self.sources(sources)
+    }
+
+    /// Detects the operating system installed under the given root directory instead of the
+    /// current one. Only supported on Linux; other platforms ignore the root directory.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Detector;
+    ///
+    /// let info = Detector::new().root("/mnt/sysroot").detect();
+    /// println!("OS information: {}", info);
+    /// ```
+    pub fn root<P: AsRef<Path>>(mut self, root: P) -> Self {
+        self.root = Some(root.as_ref().to_path_buf());
+        self
+    }
+
+    /// Sets the maximal time each external command is allowed to run. A command that takes
+    /// longer is killed and treated as failed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use os_info::Detector;
+    ///
+    /// let info = Detector::new().timeout(Duration::from_millis(500)).detect();
+    /// println!("OS information: {}", info);
+    /// ```
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Detects the operating system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Detector;
+    ///
+    /// let info = Detector::new().detect();
+    /// assert_eq!(info.os_type(), os_info::get().os_type());
+    /// ```
+    pub fn detect(&self) -> Info {
+        let overridden = match self.root {
+            Some(_) => None,
+            None => testing::overridden(),
+        };
+        if let Some(info) = overridden {
+            return info;
+        }
+
+        let mut info = imp::current_platform(self);
+        if info.codename.is_none() {
+            info.codename = lookup_codename(info.os_type, &info.version).map(str::to_owned);
+        }
+        info
+    }
+
+    /// Detects the operating system without blocking the calling thread. See `os_info::get_async`
+    /// for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Detector;
+    ///
+    /// async fn print_os_info() {
+    ///     let info = Detector::new().detect_async().await;
+    ///     println!("OS information: {}", info);
+    /// }
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn detect_async(&self) -> impl Future<Output = Info> {
+        Detection::spawn(self.clone())
+    }
+
+    /// Detects the operating system, returning an error if it couldn't be detected. See
+    /// `os_info::try_get` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Detector;
+    ///
+    /// match Detector::new().try_detect() {
+    ///     Ok(info) => println!("OS information: {}", info),
+    ///     Err(e) => println!("Unable to detect the operating system: {}", e),
+    /// }
+    /// ```
+    pub fn try_detect(&self) -> Result<Info, DetectionError> {
+        let info = self.detect();
+        info.provenance.result().map(|()| info)
+    }
+
+    /// Returns `true` if the given source is consulted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Detector, Source};
+    ///
+    /// let detector = Detector::new().without(Source::Getconf);
+    /// assert!(!detector.uses(Source::Getconf));
+    /// ```
+    pub fn uses(&self, source: Source) -> bool {
+        self.sources.contains(&source)
+    }
+}
+
+impl Default for Detector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[cfg(not(feature = "no-subprocess"))]
+    #[test]
+    fn default() {
+        let detector = Detector::default();
+        assert_eq!(detector.sources, DEFAULT_SOURCES);
+        assert_eq!(detector.root, None);
+        assert_eq!(detector.timeout, None);
+    }
+
+    #[test]
+    fn sources() {
+        let detector = Detector::new().sources(vec![
+            Source::OsRelease,
+            Source::LsbRelease,
+            Source::OsRelease,
+        ]);
+        assert_eq!(detector.sources, [Source::OsRelease, Source::LsbRelease]);
+        assert!(detector.uses(Source::LsbRelease));
+        assert!(!detector.uses(Source::Getconf));
+
+        let detector = Detector::new()
+            .sources(DEFAULT_SOURCES.to_vec())
+            .without(Source::LsbRelease);
+        assert_eq!(
+            detector.sources,
+            [
+                Source::ReleaseFile,
+                Source::OsRelease,
+                Source::Command,
+                Source::Getconf
+            ]
+        );
+    }
+
+    #[test]
+    fn subprocess_free() {
+        let expected = [
+            Source::LsbReleaseFile,
+            Source::ReleaseFile,
+            Source::OsRelease,
+            Source::Sysctl,
+        ];
+
+        let detector = Detector::new()
+            .sources(DEFAULT_SOURCES.to_vec())
+            .subprocess_free();
+        assert_eq!(detector.sources, expected);
+
+        #[cfg(feature = "no-subprocess")]
+        assert_eq!(Detector::default().sources, expected);
+
+        let detector = Detector::new()
+            .sources(vec![Source::Getconf, Source::OsRelease, Source::Command])
+            .subprocess_free();
+        assert_eq!(detector.sources, [Source::Sysctl, Source::OsRelease]);
+    }
+
+    #[test]
+    fn detect() {
+        assert_eq!(Detector::new().detect(), crate::get());
+    }
+}
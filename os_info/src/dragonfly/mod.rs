@@ -2,21 +2,42 @@ use std::process::Command;
 
 use log::trace;
 
-use crate::{bitness, uname::uname, Bitness, Info, Type, Version};
-
-pub fn current_platform() -> Info {
-    trace!("dragonfly::current_platform is called");
-
-    let version = uname()
-        .map(Version::from_string)
+use crate::{
+    architecture, bitness, uname::uname, Bitness, Detector, Info, Provenance, Source, SourceStatus,
+    Type, Version,
+};
+
+pub fn current_platform(detector: &Detector) -> Info {
+    trace!("dragonfly::current_platform({:?}) is called", detector);
+
+    let mut provenance = Provenance::default();
+    let kernel = match uname() {
+        Ok(kernel) => {
+            provenance.record(Source::Uname, "uname", SourceStatus::Parsed);
+            Some(kernel)
+        }
+        Err(e) => {
+            provenance.record_error(Source::Uname, "uname", e);
+            provenance.record_target();
+            None
+        }
+    };
+    let version = kernel
+        .as_ref()
+        .map(|k| Version::from_string(k.release()))
         .unwrap_or_else(|| Version::Unknown);
 
-    let info = Info {
+    let architecture = architecture::get();
+    let mut info = Info {
         os_type: Type::DragonFly,
         version,
-        bitness: bitness::get(),
+        bitness: bitness::detect(detector, architecture),
+        architecture,
+        kernel,
         ..Default::default()
     };
+    provenance.select(&info);
+    info.provenance = provenance;
 
     trace!("Returning {:?}", info);
     info
@@ -29,7 +50,7 @@ mod tests {
 
     #[test]
     fn os_type() {
-        let version = current_platform();
+        let version = current_platform(&Detector::default());
         assert_eq!(Type::DragonFly, version.os_type());
     }
 }
@@ -1,12 +1,17 @@
 use log::trace;
 
-use crate::{Bitness, Info, Type};
+use crate::{Bitness, Detector, Info, Provenance, Type};
 
 // TODO: Somehow get the real OS version?
-pub fn current_platform() -> Info {
-    trace!("emscripten::current_platform is called");
+pub fn current_platform(detector: &Detector) -> Info {
+    trace!("emscripten::current_platform({:?}) is called", detector);
 
-    let info = Info::with_type(Type::Emscripten);
+    let mut provenance = Provenance::default();
+    provenance.record_target();
+
+    let mut info = Info::with_type(Type::Emscripten);
+    provenance.select(&info);
+    info.provenance = provenance;
     trace!("Returning {:?}", info);
     info
 }
@@ -18,7 +23,7 @@ mod tests {
 
     #[test]
     fn os_type() {
-        let version = current_platform();
+        let version = current_platform(&Detector::default());
         assert_eq!(Type::Emscripten, version.os_type());
     }
 }
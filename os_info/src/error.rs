@@ -0,0 +1,170 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    io,
+    path::PathBuf,
+};
+
+/// An error that occurred while detecting the operating system.
+///
+/// Errors are collected for every consulted source (see `SourceRecord::error`), and `try_get`
+/// returns the most relevant one if the operating system couldn't be detected.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum DetectionError {
+    /// A file doesn't exist or can't be read.
+    FileUnreadable {
+        /// Path of the file.
+        path: PathBuf,
+        /// Kind of the I/O error.
+        kind: io::ErrorKind,
+        /// Description of the I/O error.
+        message: String,
+    },
+    /// A command isn't installed.
+    CommandMissing {
+        /// The command line.
+        command: String,
+    },
+    /// A command couldn't be executed or exited unsuccessfully.
+    CommandFailed {
+        /// The command line.
+        command: String,
+        /// Description of the failure.
+        message: String,
+    },
+    /// A system call failed.
+    SystemCallFailed {
+        /// Name of the system call or API function.
+        name: String,
+        /// Description of the failure.
+        message: String,
+    },
+    /// The content of a file or the output of a command wasn't recognized.
+    UnparseableContent {
+        /// The file path or the command line.
+        location: String,
+        /// Description of what is wrong.
+        message: String,
+    },
+    /// None of the sources identified the operating system.
+    Undetected,
+}
+
+impl DetectionError {
+    pub(crate) fn file(path: PathBuf, error: &io::Error) -> Self {
+        DetectionError::FileUnreadable {
+            path,
+            kind: error.kind(),
+            message: error.to_string(),
+        }
+    }
+
+    pub(crate) fn command(command: &str, error: &io::Error) -> Self {
+        if error.kind() == io::ErrorKind::NotFound {
+            DetectionError::CommandMissing {
+                command: command.to_owned(),
+            }
+        } else {
+            DetectionError::CommandFailed {
+                command: command.to_owned(),
+                message: error.to_string(),
+            }
+        }
+    }
+
+    /// Returns `true` if the error means that the source doesn't exist, which is expected for
+    /// most sources on any particular system.
+    pub(crate) fn is_missing(&self) -> bool {
+        match self {
+            DetectionError::FileUnreadable { kind, .. } => *kind == io::ErrorKind::NotFound,
+            DetectionError::CommandMissing { .. } => true,
+            _ => false,
+        }
+    }
+}
+
+impl Display for DetectionError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            DetectionError::FileUnreadable { path, message, .. } => {
+                write!(f, "unable to read {:?}: {}", path, message)
+            }
+            DetectionError::CommandMissing { command } => {
+                write!(f, "command '{}' not found", command)
+            }
+            DetectionError::CommandFailed { command, message } => {
+                write!(f, "command '{}' failed: {}", command, message)
+            }
+            DetectionError::SystemCallFailed { name, message } => {
+                write!(f, "'{}' failed: {}", name, message)
+            }
+            DetectionError::UnparseableContent { location, message } => {
+                write!(f, "unable to parse {}: {}", location, message)
+            }
+            DetectionError::Undetected => write!(f, "unable to detect the operating system"),
+        }
+    }
+}
+
+impl Error for DetectionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn display() {
+        let not_found = io::Error::from(io::ErrorKind::NotFound);
+        let data = [
+            (
+                DetectionError::file(PathBuf::from("/etc/os-release"), &not_found),
+                format!("unable to read \"/etc/os-release\": {}", not_found),
+            ),
+            (
+                DetectionError::command("lsb_release -a", &not_found),
+                "command 'lsb_release -a' not found".to_owned(),
+            ),
+            (
+                DetectionError::CommandFailed {
+                    command: "sw_vers".to_owned(),
+                    message: "exit status: 1".to_owned(),
+                },
+                "command 'sw_vers' failed: exit status: 1".to_owned(),
+            ),
+            (
+                DetectionError::UnparseableContent {
+                    location: "/etc/os-release".to_owned(),
+                    message: "unknown NAME".to_owned(),
+                },
+                "unable to parse /etc/os-release: unknown NAME".to_owned(),
+            ),
+            (
+                DetectionError::Undetected,
+                "unable to detect the operating system".to_owned(),
+            ),
+        ];
+
+        for (error, expected) in &data {
+            assert_eq!(&error.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn is_missing() {
+        let not_found = io::Error::from(io::ErrorKind::NotFound);
+        let denied = io::Error::from(io::ErrorKind::PermissionDenied);
+        let data = [
+            (DetectionError::file(PathBuf::from("a"), &not_found), true),
+            (DetectionError::file(PathBuf::from("a"), &denied), false),
+            (DetectionError::command("a", &not_found), true),
+            (DetectionError::command("a", &denied), false),
+            (DetectionError::Undetected, false),
+        ];
+
+        for (error, expected) in &data {
+            assert_eq!(error.is_missing(), *expected, "{:?}", error);
+        }
+    }
+}
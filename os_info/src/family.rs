@@ -0,0 +1,133 @@
+// spell-checker:ignore rhel, sles, opensuse, archlinux, raspbian, almalinux, rocky, postmarketos, funtoo
+
+use std::fmt::{self, Display, Formatter};
+
+use super::OsRelease;
+
+/// A lineage of operating systems, for example, all distributions derived from Debian.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::upper_case_acronyms)]
+#[non_exhaustive]
+pub enum Family {
+    /// Debian and its derivatives such as Ubuntu or Linux Mint.
+    Debian,
+    /// Red Hat Enterprise Linux, Fedora and their derivatives such as CentOS or Oracle Linux.
+    RedHat,
+    /// SUSE Linux Enterprise and openSUSE.
+    SUSE,
+    /// Arch Linux and its derivatives such as Manjaro.
+    Arch,
+    /// Alpine Linux and its derivatives.
+    Alpine,
+    /// Gentoo and its derivatives.
+    Gentoo,
+    /// An operating system that isn't derived from any of the other families.
+    Independent,
+    /// Unknown family.
+    #[default]
+    Unknown,
+}
+
+impl Family {
+    /// Returns the family with the given `os-release` identifier (`ID` or an `ID_LIKE` entry).
+    pub(crate) fn from_os_release_id(id: &str) -> Option<Self> {
+        match id {
+            "debian" | "ubuntu" | "raspbian" => Some(Family::Debian),
+            "rhel" | "fedora" | "centos" => Some(Family::RedHat),
+            "suse" | "opensuse" | "sles" => Some(Family::SUSE),
+            "arch" | "archlinux" => Some(Family::Arch),
+            "alpine" => Some(Family::Alpine),
+            "gentoo" => Some(Family::Gentoo),
+            _ => None,
+        }
+    }
+
+    /// Determines the family from the `ID` and `ID_LIKE` fields of the `os-release` file. The
+    /// closest relative with a known family wins.
+    pub(crate) fn from_os_release(os_release: &OsRelease) -> Option<Self> {
+        os_release
+            .id()
+            .into_iter()
+            .chain(os_release.id_like())
+            .find_map(Family::from_os_release_id)
+    }
+}
+
+impl Display for Family {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Family::RedHat => write!(f, "Red Hat"),
+            _ => write!(f, "{:?}", self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn default() {
+        assert_eq!(Family::Unknown, Family::default());
+    }
+
+    #[test]
+    fn os_release_ids() {
+        let data = [
+            ("ID=ubuntu\nID_LIKE=debian", Some(Family::Debian)),
+            (
+                "ID=linuxmint\nID_LIKE=\"ubuntu debian\"",
+                Some(Family::Debian),
+            ),
+            (
+                "ID=almalinux\nID_LIKE=\"rhel centos fedora\"",
+                Some(Family::RedHat),
+            ),
+            (
+                "ID=\"rocky\"\nID_LIKE=\"rhel centos fedora\"",
+                Some(Family::RedHat),
+            ),
+            (
+                "ID=\"opensuse-leap\"\nID_LIKE=\"suse opensuse\"",
+                Some(Family::SUSE),
+            ),
+            ("ID=manjaro\nID_LIKE=arch", Some(Family::Arch)),
+            ("ID=postmarketos\nID_LIKE=alpine", Some(Family::Alpine)),
+            ("ID=funtoo\nID_LIKE=gentoo", Some(Family::Gentoo)),
+            ("ID=fedora", Some(Family::RedHat)),
+            ("ID=nixos", None),
+            ("", None),
+        ];
+
+        for (content, expected) in &data {
+            let os_release = OsRelease::parse(content);
+            assert_eq!(
+                Family::from_os_release(&os_release),
+                *expected,
+                "{}",
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn display() {
+        let data = [
+            (Family::Debian, "Debian"),
+            (Family::RedHat, "Red Hat"),
+            (Family::SUSE, "SUSE"),
+            (Family::Arch, "Arch"),
+            (Family::Alpine, "Alpine"),
+            (Family::Gentoo, "Gentoo"),
+            (Family::Independent, "Independent"),
+            (Family::Unknown, "Unknown"),
+        ];
+
+        for (family, expected) in &data {
+            assert_eq!(family.to_string(), *expected);
+        }
+    }
+}
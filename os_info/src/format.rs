@@ -0,0 +1,93 @@
+use std::fmt::{self, Display, Formatter};
+
+use super::{Architecture, Info, Version};
+
+/// A way of rendering `Info` as text, used with `Info::display`.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::{Format, Info, Type};
+///
+/// let info = Info::with_type(Type::Ubuntu);
+/// assert_eq!("Ubuntu", info.display(Format::Short).to_string());
+/// assert_eq!("Ubuntu [unknown bitness]", info.display(Format::Standard).to_string());
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Type and version, for example, `Ubuntu 18.10.0`.
+    Short,
+    /// Type, version, edition, codename and bitness, for example,
+    /// `Ubuntu 18.10.0 (cosmic) [64-bit]`. This format is used by `Display`.
+    #[default]
+    Standard,
+    /// The `PRETTY_NAME` field of the `os-release` file, for example, `Ubuntu 18.10`, or a name
+    /// made up of type, version, edition and codename if there is no such field.
+    Pretty,
+    /// The standard format followed by the architecture and the kernel, for example,
+    /// `Ubuntu 18.10.0 (cosmic) [64-bit], x86_64, Linux 4.18.0-25-generic`. This format is used by
+    /// the alternate form of `Display` (`{:#}`).
+    Long,
+}
+
+/// `Info` rendered in the given format.
+pub(crate) struct Formatted<'a> {
+    pub(crate) info: &'a Info,
+    pub(crate) format: Format,
+}
+
+impl Display for Formatted<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let info = self.info;
+        match self.format {
+            Format::Short => short(info, f),
+            Format::Standard => standard(info, f),
+            Format::Pretty => match info
+                .os_release()
+                .and_then(|r| r.pretty_name())
+                .filter(|name| !name.is_empty())
+            {
+                Some(name) => f.write_str(name),
+                None => {
+                    short(info, f)?;
+                    details(info, f)
+                }
+            },
+            Format::Long => {
+                standard(info, f)?;
+                if info.architecture() != Architecture::Unknown {
+                    write!(f, ", {}", info.architecture())?;
+                }
+                if let Some(kernel) = info.kernel() {
+                    write!(f, ", {}", kernel)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+fn short(info: &Info, f: &mut Formatter) -> fmt::Result {
+    write!(f, "{}", info.os_type())?;
+    if *info.version() != Version::Unknown {
+        write!(f, " {}", info.version())?;
+    }
+    Ok(())
+}
+
+fn standard(info: &Info, f: &mut Formatter) -> fmt::Result {
+    short(info, f)?;
+    details(info, f)?;
+    write!(f, " [{}]", info.bitness())
+}
+
+/// Writes the edition and the codename in parentheses.
+fn details(info: &Info, f: &mut Formatter) -> fmt::Result {
+    if let Some(edition) = info.edition() {
+        write!(f, " ({})", edition)?;
+    }
+    if let Some(codename) = info.codename() {
+        write!(f, " ({})", codename)?;
+    }
+    Ok(())
+}
@@ -1,37 +1,52 @@
-use std::process::Command;
-use std::str;
+use log::trace;
 
-use log::{error, trace};
+use crate::{
+    architecture, bitness, uname::uname, Detector, Info, Provenance, Source, SourceStatus, Type,
+    Version,
+};
 
-use crate::{bitness, uname::uname, Info, Type, Version};
+pub fn current_platform(detector: &Detector) -> Info {
+    trace!("freebsd::current_platform({:?}) is called", detector);
 
-pub fn current_platform() -> Info {
-    trace!("freebsd::current_platform is called");
-
-    let version = uname()
-        .map(Version::from_string)
+    let mut provenance = Provenance::default();
+    let kernel = match uname() {
+        Ok(kernel) => {
+            provenance.record(Source::Uname, "uname", SourceStatus::Parsed);
+            Some(kernel)
+        }
+        Err(e) => {
+            provenance.record_error(Source::Uname, "uname", e);
+            provenance.record_target();
+            None
+        }
+    };
+    let version = kernel
+        .as_ref()
+        .map(|k| Version::from_string(k.release()))
         .unwrap_or_else(|| Version::Unknown);
 
-    let info = Info {
-        os_type: get_os(),
+    let architecture = architecture::get();
+    let mut info = Info {
+        os_type: kernel
+            .as_ref()
+            .map_or(Type::Unknown, |k| get_os(k.sysname())),
         version,
-        bitness: bitness::get(),
+        bitness: bitness::detect(detector, architecture),
+        architecture,
+        kernel,
         ..Default::default()
     };
+    provenance.select(&info);
+    info.provenance = provenance;
 
     trace!("Returning {:?}", info);
     info
 }
 
-fn get_os() -> Type {
-    let os = Command::new("uname")
-        .arg("-s")
-        .output()
-        .expect("Failed to get OS");
-
-    match str::from_utf8(&os.stdout).unwrap() {
-        "FreeBSD\n" => Type::FreeBSD,
-        "MidnightBSD\n" => Type::MidnightBSD,
+fn get_os(sysname: &str) -> Type {
+    match sysname {
+        "FreeBSD" => Type::FreeBSD,
+        "MidnightBSD" => Type::MidnightBSD,
         _ => Type::Unknown,
     }
 }
@@ -43,7 +58,7 @@ mod tests {
 
     #[test]
     fn os_type() {
-        let version = current_platform();
+        let version = current_platform(&Detector::default());
         assert_eq!(Type::FreeBSD, version.os_type());
     }
 }
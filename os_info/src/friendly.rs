@@ -0,0 +1,382 @@
+//! A human-friendly serialized form of `Info` for consumers written in other languages. Available
+//! with the `serde` feature.
+//!
+//! The derived implementation of `Serialize` for `Info` mirrors the Rust types, for example,
+//! `{"Semantic":[20,4,0]}` for a version. The functions of this module use the following form
+//! instead:
+//!
+//! ```json
+//! {
+//!   "schema_version": 1,
+//!   "type": "ubuntu",
+//!   "version": "20.4.0",
+//!   "edition": null,
+//!   "codename": "focal",
+//!   "bitness": 64,
+//!   "architecture": "x86_64",
+//!   "kernel": null,
+//!   "os_release": null,
+//!   "upstream": null
+//! }
+//! ```
+//!
+//! The type is written as `Type::id`, the version as its string representation (`rolling` or
+//! `rolling:<date>` for rolling releases), and the bitness as a number. Unknown values are `null`.
+//! The stored base distribution of a derivative (see `Info::upstream`) is written in the same form.
+//! The provenance isn't serialized. `deserialize` accepts both this form and the derived one, so
+//! data written by earlier versions of the crate can still be read.
+//!
+//! The form is lossy in a few cases. Types that aren't known in the reading process (custom types
+//! that weren't registered with `Distribution`) are read as `Type::Unknown`. Versions are parsed
+//! from their string again, so a `Version::Custom` that looks like a number (`22.04`) is read as
+//! `Version::Semantic`, and one that is `rolling` as `Version::Rolling`.
+//!
+//! The functions can be used with the `with` attribute of serde or called directly.
+//!
+//! # Examples
+//!
+//! ```
+//! use os_info::{Info, Type};
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Report {
+//!     #[serde(with = "os_info::friendly")]
+//!     os: Info,
+//! }
+//!
+//! let report = Report { os: Info::with_type(Type::Fedora) };
+//! let json = serde_json::to_string(&report).unwrap();
+//! assert!(json.contains(r#""type":"fedora""#));
+//!
+//! let report: Report = serde_json::from_str(&json).unwrap();
+//! assert_eq!(Type::Fedora, report.os.os_type());
+//! ```
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{Architecture, Bitness, Info, KernelInfo, OsRelease, Type, Version};
+
+/// The version of the serialized form written by `serialize`.
+pub const SCHEMA_VERSION: u32 = 1;
+
+const ROLLING: &str = "rolling";
+
+#[derive(Serialize)]
+struct FriendlyRef<'a> {
+    schema_version: u32,
+    #[serde(rename = "type")]
+    os_type: &'static str,
+    version: Option<String>,
+    edition: Option<&'a str>,
+    codename: Option<&'a str>,
+    bitness: Option<u8>,
+    architecture: Option<String>,
+    kernel: Option<&'a KernelInfo>,
+    os_release: Option<&'a OsRelease>,
+    upstream: Option<UpstreamRef<'a>>,
+}
+
+/// The base distribution, serialized in the same form.
+struct UpstreamRef<'a>(&'a Info);
+
+impl Serialize for UpstreamRef<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self.0, serializer)
+    }
+}
+
+#[derive(Deserialize)]
+struct Upstream(#[serde(deserialize_with = "deserialize_boxed")] Box<Info>);
+
+#[derive(Deserialize)]
+struct Friendly {
+    schema_version: u32,
+    #[serde(rename = "type")]
+    os_type: String,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    edition: Option<String>,
+    #[serde(default)]
+    codename: Option<String>,
+    #[serde(default)]
+    bitness: Option<u8>,
+    #[serde(default)]
+    architecture: Option<String>,
+    #[serde(default)]
+    kernel: Option<KernelInfo>,
+    #[serde(default)]
+    os_release: Option<OsRelease>,
+    #[serde(default)]
+    upstream: Option<Upstream>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AnyForm {
+    Friendly(Friendly),
+    Legacy(Info),
+}
+
+/// Serializes `Info` in the human-friendly form.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::{Bitness, Info, Type, Version};
+///
+/// let info = Info::builder(Type::Ubuntu)
+///     .version(Version::Semantic(20, 4, 0))
+///     .bitness(Bitness::X64)
+///     .build()
+///     .unwrap();
+///
+/// let mut json = Vec::new();
+/// os_info::friendly::serialize(&info, &mut serde_json::Serializer::new(&mut json)).unwrap();
+/// let json = String::from_utf8(json).unwrap();
+/// assert!(json.contains(r#""version":"20.4.0""#));
+/// assert!(json.contains(r#""bitness":64"#));
+/// ```
+pub fn serialize<S: Serializer>(info: &Info, serializer: S) -> Result<S::Ok, S::Error> {
+    FriendlyRef {
+        schema_version: SCHEMA_VERSION,
+        os_type: info.os_type().id(),
+        version: version_to_string(info.version()),
+        edition: info.edition(),
+        codename: info.codename(),
+        bitness: match info.bitness() {
+            Bitness::Unknown => None,
+            Bitness::X32 => Some(32),
+            Bitness::X64 => Some(64),
+        },
+        architecture: match info.architecture() {
+            Architecture::Unknown => None,
+            architecture => Some(architecture.to_string()),
+        },
+        kernel: info.kernel(),
+        os_release: info.os_release(),
+        upstream: info.upstream.as_deref().map(UpstreamRef),
+    }
+    .serialize(serializer)
+}
+
+/// Deserializes `Info` from the human-friendly or the derived form.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::{Bitness, Type};
+///
+/// let json = r#"{"schema_version":1,"type":"rhel","version":"8.9.0","bitness":64}"#;
+/// let info = os_info::friendly::deserialize(&mut serde_json::Deserializer::from_str(json))
+///     .unwrap();
+/// assert_eq!(Type::RedHatEnterprise, info.os_type());
+/// assert_eq!(Bitness::X64, info.bitness());
+/// ```
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Info, D::Error> {
+    let friendly = match AnyForm::deserialize(deserializer)? {
+        AnyForm::Friendly(friendly) => friendly,
+        AnyForm::Legacy(info) => return Ok(info),
+    };
+
+    if friendly.schema_version > SCHEMA_VERSION {
+        return Err(de::Error::custom(format!(
+            "unsupported schema version {}",
+            friendly.schema_version
+        )));
+    }
+
+    let Friendly {
+        schema_version: _,
+        os_type,
+        version,
+        edition,
+        codename,
+        bitness,
+        architecture,
+        kernel,
+        os_release,
+        upstream,
+    } = friendly;
+    let bitness = match bitness {
+        None => Bitness::Unknown,
+        Some(32) => Bitness::X32,
+        Some(64) => Bitness::X64,
+        Some(bits) => return Err(de::Error::custom(format!("invalid bitness {}", bits))),
+    };
+    let os_type = os_type.parse().unwrap_or(Type::Unknown);
+
+    let mut info = Info::with_type(os_type);
+    info.version = version
+        .as_deref()
+        .map_or(Version::Unknown, version_from_str);
+    info.edition = edition;
+    info.codename = codename;
+    info.bitness = bitness;
+    info.architecture = architecture
+        .as_deref()
+        .map_or(Architecture::Unknown, Architecture::from_machine);
+    info.kernel = kernel;
+    info.os_release = os_release;
+    info.upstream = upstream.map(|Upstream(upstream)| upstream);
+    Ok(info)
+}
+
+fn deserialize_boxed<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<Info>, D::Error> {
+    deserialize(deserializer).map(Box::new)
+}
+
+fn version_to_string(version: &Version) -> Option<String> {
+    match version {
+        Version::Unknown => None,
+        Version::Rolling(None) => Some(ROLLING.to_owned()),
+        Version::Rolling(Some(date)) => Some(format!("{}:{}", ROLLING, date)),
+        version => Some(version.to_string()),
+    }
+}
+
+fn version_from_str(version: &str) -> Version {
+    match version.strip_prefix(ROLLING) {
+        Some("") => Version::Rolling(None),
+        Some(date) if date.starts_with(':') => Version::Rolling(Some(date[1..].to_owned())),
+        _ => Version::from_string(version),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn to_json(info: &Info) -> String {
+        let mut json = Vec::new();
+        serialize(info, &mut serde_json::Serializer::new(&mut json)).unwrap();
+        String::from_utf8(json).unwrap()
+    }
+
+    fn from_json(json: &str) -> Result<Info, serde_json::Error> {
+        deserialize(&mut serde_json::Deserializer::from_str(json))
+    }
+
+    #[test]
+    fn serialize_info() {
+        let info = Info {
+            version: Version::Semantic(20, 4, 0),
+            codename: Some("focal".to_owned()),
+            bitness: Bitness::X64,
+            architecture: Architecture::X86_64,
+            ..Info::with_type(Type::openSUSE)
+        };
+        assert_eq!(
+            to_json(&info),
+            r#"{"schema_version":1,"type":"opensuse","version":"20.4.0","edition":null,"codename":"focal","bitness":64,"architecture":"x86_64","kernel":null,"os_release":null,"upstream":null}"#
+        );
+        assert_eq!(
+            to_json(&Info::unknown()),
+            r#"{"schema_version":1,"type":"unknown","version":null,"edition":null,"codename":null,"bitness":null,"architecture":null,"kernel":null,"os_release":null,"upstream":null}"#
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let data = [
+            Info::unknown(),
+            Info {
+                version: Version::Semantic(22, 4, 0),
+                edition: Some("Server".to_owned()),
+                codename: Some("jammy".to_owned()),
+                bitness: Bitness::X32,
+                architecture: Architecture::Armv7,
+                kernel: Some(KernelInfo::new("Linux", "5.15.0", "#1", "armv7l")),
+                os_release: Some(OsRelease::parse("ID=ubuntu")),
+                ..Info::with_type(Type::Ubuntu)
+            },
+            Info {
+                version: Version::Rolling(Some("2020.05.24".to_owned())),
+                ..Info::with_type(Type::Manjaro)
+            },
+            Info {
+                version: Version::Rolling(None),
+                ..Info::with_type(Type::Arch)
+            },
+            Info {
+                version: Version::Semantic(20, 0, 0),
+                upstream: Some(Box::new(Info {
+                    version: Version::Semantic(20, 4, 0),
+                    codename: Some("focal".to_owned()),
+                    ..Info::with_type(Type::Ubuntu)
+                })),
+                ..Info::with_type(Type::Mint)
+            },
+            Info {
+                version: Version::Extended(vec![10, 0, 19045, 3930], None),
+                ..Info::with_type(Type::Windows)
+            },
+            Info {
+                version: Version::Custom("Special Version".to_owned()),
+                ..Info::with_type(Type::Redox)
+            },
+        ];
+
+        for info in &data {
+            let decoded = from_json(&to_json(info)).unwrap();
+            assert_eq!(decoded, *info);
+            assert_eq!(decoded.upstream, info.upstream);
+        }
+    }
+
+    #[test]
+    fn legacy_form() {
+        let info = Info {
+            version: Version::Semantic(20, 4, 0),
+            bitness: Bitness::X64,
+            ..Info::with_type(Type::RedHatEnterprise)
+        };
+        let legacy = serde_json::to_string(&info).unwrap();
+        assert!(legacy.contains(r#""Semantic":[20,4,0]"#));
+        assert_eq!(from_json(&legacy).unwrap(), info);
+    }
+
+    #[test]
+    fn unknown_type() {
+        let info = from_json(r#"{"schema_version":1,"type":"friendly-unknown"}"#).unwrap();
+        assert_eq!(info.os_type(), Type::Unknown);
+        assert_eq!(info.version(), &Version::Unknown);
+    }
+
+    #[test]
+    fn lossy_versions() {
+        let data = [
+            (
+                Version::Custom("22.04".to_owned()),
+                Version::Semantic(22, 4, 0),
+            ),
+            (
+                Version::Custom("rolling".to_owned()),
+                Version::Rolling(None),
+            ),
+        ];
+
+        for (version, expected) in &data {
+            let info = Info {
+                version: version.clone(),
+                ..Info::with_type(Type::Redox)
+            };
+            assert_eq!(from_json(&to_json(&info)).unwrap().version(), expected);
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let data = [
+            r#"{"schema_version":2,"type":"debian"}"#,
+            r#"{"schema_version":1,"type":"debian","bitness":16}"#,
+            r#"{"version":"1.0"}"#,
+        ];
+
+        for json in &data {
+            assert!(from_json(json).is_err(), "{}", json);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::{
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+use log::warn;
+
+use crate::{testing, Detector, Info};
+
+/// Returns information about the current operating system (type, version, edition, etc.) without
+/// blocking the calling thread.
+///
+/// The detection reads files and may run commands, so it is performed on a dedicated thread and
+/// the returned future completes when it is done. The future doesn't depend on any particular
+/// runtime. The result is the same as of `get`.
+///
+/// # Examples
+///
+/// ```
+/// async fn print_os_info() {
+///     let info = os_info::get_async().await;
+///     println!("OS information: {}", info);
+/// }
+/// ```
+pub async fn get_async() -> Info {
+    Detector::default().detect_async().await
+}
+
+type Outcome = thread::Result<Info>;
+
+/// The state shared between a `Detection` future and its thread.
+#[derive(Default)]
+struct Shared {
+    outcome: Option<Outcome>,
+    waker: Option<Waker>,
+}
+
+/// A future that completes when the detection thread finishes.
+pub(crate) struct Detection(Arc<Mutex<Shared>>);
+
+impl Detection {
+    pub(crate) fn spawn(detector: Detector) -> Self {
+        let shared = Arc::new(Mutex::new(Shared::default()));
+        let thread_shared = Arc::clone(&shared);
+        let fallback = detector.clone();
+        // The override is thread-local, so it's moved to the detection thread.
+        let overridden = testing::scoped();
+        let spawned = thread::Builder::new()
+            .name("os_info".to_owned())
+            .spawn(move || {
+                let _guard = overridden.map(testing::override_with);
+                let outcome = panic::catch_unwind(AssertUnwindSafe(|| detector.detect()));
+                let mut shared = lock(&thread_shared);
+                shared.outcome = Some(outcome);
+                if let Some(waker) = shared.waker.take() {
+                    waker.wake();
+                }
+            });
+
+        if let Err(e) = spawned {
+            warn!("Unable to spawn a detection thread: {:?}", e);
+            lock(&shared).outcome = Some(Ok(fallback.detect()));
+        }
+        Detection(shared)
+    }
+}
+
+impl Future for Detection {
+    type Output = Info;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Info> {
+        let mut shared = lock(&self.0);
+        match shared.outcome.take() {
+            Some(Ok(info)) => Poll::Ready(info),
+            // Propagate a panic that occurred during the detection to the awaiting task.
+            Some(Err(payload)) => panic::resume_unwind(payload),
+            None => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+fn lock(shared: &Mutex<Shared>) -> MutexGuard<'_, Shared> {
+    shared.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use std::task::Wake;
+
+    struct ThreadWaker(thread::Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    /// A minimal executor that parks the current thread until the future is woken.
+    fn block_on<F: Future>(future: F) -> F::Output {
+        let mut future = Box::pin(future);
+        let waker = Waker::from(Arc::new(ThreadWaker(thread::current())));
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            match future.as_mut().poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn same_as_get() {
+        assert_eq!(block_on(get_async()), crate::get());
+    }
+
+    #[test]
+    fn detector() {
+        let detector = Detector::new().without(crate::Source::LsbRelease);
+        assert_eq!(block_on(detector.detect_async()), detector.detect());
+    }
+
+    #[test]
+    fn overridden() {
+        let _guard = testing::override_with(Info::with_type(crate::Type::Macos));
+        assert_eq!(block_on(get_async()).os_type(), crate::Type::Macos);
+    }
+}
@@ -1,8 +1,15 @@
 // spell-checker:ignore itertools, iproduct, bitnesses
 
-use std::fmt::{self, Display, Formatter};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+    hash::{Hash, Hasher},
+};
 
-use super::{Bitness, Type, Version};
+use super::{
+    format::Formatted, lifecycle, upstream, Architecture, Bitness, Family, Format, InfoBuilder,
+    KernelInfo, Lifecycle, OsRelease, Provenance, Requirement, Type, Version,
+};
 
 /// Holds information about operating system (type, version, etc.).
 ///
@@ -17,8 +24,12 @@ use super::{Bitness, Type, Version};
 /// let info = os_info::get();
 /// println!("OS information: {}", info);
 /// ```
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// Comparisons and hashing only take the detected values into account: the provenance and the base
+/// distribution stored during the detection are ignored.
+#[derive(Debug, Clone)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
 pub struct Info {
     /// Operating system type. See `Type` for details.
     pub(crate) os_type: Type,
@@ -26,11 +37,25 @@ pub struct Info {
     pub(crate) version: Version,
     /// Operating system edition.
     pub(crate) edition: Option<String>,
-    /// Operating system edition.
+    /// Operating system codename.
     pub(crate) codename: Option<String>,
     /// Operating system architecture in terms of how many bits compose the basic values it can deal
     /// with. See `Bitness` for details.
     pub(crate) bitness: Bitness,
+    /// Processor architecture of the operating system. See `Architecture` for details.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) architecture: Architecture,
+    /// Information about the running kernel on Unix systems. See `KernelInfo` for details.
+    pub(crate) kernel: Option<KernelInfo>,
+    /// Contents of the `os-release` file, if the operating system provides one.
+    pub(crate) os_release: Option<OsRelease>,
+    /// Sources consulted while detecting the operating system. See `Provenance` for details.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) provenance: Provenance,
+    /// The base distribution of a derivative, if it was read from a dedicated file during the
+    /// detection. See `Info::upstream` for details.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) upstream: Option<Box<Info>>,
 }
 
 impl Info {
@@ -55,6 +80,11 @@ impl Info {
             edition: None,
             codename: None,
             bitness: Bitness::Unknown,
+            architecture: Architecture::Unknown,
+            kernel: None,
+            os_release: None,
+            provenance: Provenance::default(),
+            upstream: None,
         }
     }
 
@@ -80,6 +110,25 @@ impl Info {
         }
     }
 
+    /// Returns a builder of `Info` with the specified operating system type. See `InfoBuilder` for
+    /// details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Info, Type, Version};
+    ///
+    /// let info = Info::builder(Type::Debian)
+    ///     .version(Version::from_string("12"))
+    ///     .codename("bookworm")
+    ///     .build()
+    ///     .unwrap();
+    /// assert_eq!(Some("bookworm"), info.codename());
+    /// ```
+    pub fn builder(os_type: Type) -> InfoBuilder {
+        InfoBuilder::new(os_type)
+    }
+
     /// Returns operating system type. See `Type` for details.
     ///
     /// # Examples
@@ -110,6 +159,10 @@ impl Info {
 
     /// Returns optional operation system edition.
     ///
+    /// On Windows this is the product edition (for example, `Windows 10 Pro`), and on Linux the
+    /// variant of the distribution (for example, `Workstation Edition` or `Server`) if it reports
+    /// one.
+    ///
     /// # Examples
     ///
     /// ```
@@ -147,6 +200,163 @@ impl Info {
     pub fn bitness(&self) -> Bitness {
         self.bitness
     }
+
+    /// Returns the processor architecture of the operating system. See `Architecture` for
+    /// details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Architecture, Info};
+    ///
+    /// let info = Info::unknown();
+    /// assert_eq!(Architecture::Unknown, info.architecture());
+    /// ```
+    pub fn architecture(&self) -> Architecture {
+        self.architecture
+    }
+
+    /// Returns information about the running kernel, if available. The kernel is reported on
+    /// Unix systems only. See `KernelInfo` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Info;
+    ///
+    /// let info = Info::unknown();
+    /// assert_eq!(None, info.kernel());
+    /// ```
+    pub fn kernel(&self) -> Option<&KernelInfo> {
+        self.kernel.as_ref()
+    }
+
+    /// Returns the parsed `os-release` file of the operating system, if it has one. See
+    /// `OsRelease` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Info;
+    ///
+    /// let info = Info::unknown();
+    /// assert_eq!(None, info.os_release());
+    /// ```
+    pub fn os_release(&self) -> Option<&OsRelease> {
+        self.os_release.as_ref()
+    }
+
+    /// Returns the description of how the operating system was detected: the consulted sources,
+    /// the one that provided the result and the confidence level. See `Provenance` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info;
+    ///
+    /// let info = os_info::get();
+    /// println!("{}", info.provenance());
+    /// ```
+    pub fn provenance(&self) -> &Provenance {
+        &self.provenance
+    }
+
+    /// Returns the family (lineage) of the operating system. See `Family` for details.
+    ///
+    /// The `ID` and `ID_LIKE` fields of the `os-release` file take precedence, so derivatives that
+    /// don't have a dedicated `Type` are still classified correctly. Otherwise the family is
+    /// determined by the operating system type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Family, Info, Type};
+    ///
+    /// let info = Info::with_type(Type::Mint);
+    /// assert_eq!(Family::Debian, info.family());
+    /// ```
+    pub fn family(&self) -> Family {
+        self.os_release
+            .as_ref()
+            .and_then(Family::from_os_release)
+            .unwrap_or_else(|| self.os_type.family())
+    }
+
+    /// Checks whether the operating system satisfies the given requirement. See `Requirement` for
+    /// details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Info, Requirement, Type};
+    ///
+    /// let requirement: Requirement = "ubuntu >= 20.04 || rhel >= 8".parse().unwrap();
+    /// let info = Info::with_type(Type::Windows);
+    /// assert!(!info.satisfies(&requirement));
+    /// ```
+    pub fn satisfies(&self, requirement: &Requirement) -> bool {
+        requirement.matches(self)
+    }
+
+    /// Returns the release and end-of-support dates of the operating system version, if they are
+    /// known. See `Lifecycle` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Date;
+    ///
+    /// let info = os_info::get();
+    /// if let Some(lifecycle) = info.lifecycle() {
+    ///     println!("End of support: {:?}", lifecycle.end_of_support());
+    ///     println!("Supported: {}", lifecycle.is_supported_on(Date::today()));
+    /// }
+    /// ```
+    pub fn lifecycle(&self) -> Option<Lifecycle> {
+        lifecycle::find(self)
+    }
+
+    /// Returns the distribution and release a derivative is based on, for example, Ubuntu 20.04
+    /// for Linux Mint 20 or Debian 12 for Raspberry Pi OS 12. `None` is returned for distributions
+    /// that aren't derivatives or whose base is unknown.
+    ///
+    /// The base is read from the `/etc/upstream-release/lsb-release` file if the distribution ships
+    /// one, and otherwise determined from the `UBUNTU_CODENAME`, `DEBIAN_CODENAME` or
+    /// `VERSION_CODENAME` field of the `os-release` file. The bitness, architecture and kernel of
+    /// the derivative are used for the base.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Info, OsRelease, Type, Version};
+    ///
+    /// let info = Info::builder(Type::Mint)
+    ///     .os_release(OsRelease::parse("ID=linuxmint\nVERSION_ID=\"20\"\nUBUNTU_CODENAME=focal"))
+    ///     .build()
+    ///     .unwrap();
+    /// let upstream = info.upstream().unwrap();
+    /// assert_eq!(Type::Ubuntu, upstream.os_type());
+    /// assert_eq!(&Version::Semantic(20, 4, 0), upstream.version());
+    /// ```
+    pub fn upstream(&self) -> Option<Info> {
+        upstream::find(self)
+    }
+
+    /// Returns an object that renders the information in the given format. See `Format` for the
+    /// available formats.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Format;
+    ///
+    /// let info = os_info::get();
+    /// println!("{}", info.display(Format::Pretty));
+    /// println!("{}", info.display(Format::Short));
+    /// ```
+    pub fn display(&self, format: Format) -> impl Display + '_ {
+        Formatted { info: self, format }
+    }
 }
 
 impl Default for Info {
@@ -155,26 +365,76 @@ impl Default for Info {
     }
 }
 
+impl Info {
+    /// The fields that take part in comparisons and hashing.
+    #[allow(clippy::type_complexity)]
+    fn key(
+        &self,
+    ) -> (
+        Type,
+        &Version,
+        &Option<String>,
+        &Option<String>,
+        Bitness,
+        Architecture,
+        &Option<KernelInfo>,
+        &Option<OsRelease>,
+    ) {
+        (
+            self.os_type,
+            &self.version,
+            &self.edition,
+            &self.codename,
+            self.bitness,
+            self.architecture,
+            &self.kernel,
+            &self.os_release,
+        )
+    }
+}
+
+impl PartialEq for Info {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Info {}
+
+impl PartialOrd for Info {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Info {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl Hash for Info {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
 impl Display for Info {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
-        write!(f, "{}", self.os_type)?;
-        if self.version != Version::Unknown {
-            write!(f, " {}", self.version)?;
-        }
-        if let Some(ref edition) = self.edition {
-            write!(f, " ({})", edition)?;
-        }
-        if let Some(ref codename) = self.codename {
-            write!(f, " ({})", codename)?;
-        }
-        write!(f, " [{}]", self.bitness)
+        let format = if f.alternate() {
+            Format::Long
+        } else {
+            Format::Standard
+        };
+        self.display(format).fmt(f)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use pretty_assertions::assert_eq;
+    use crate::{Source, SourceStatus};
+    use pretty_assertions::{assert_eq, assert_ne};
 
     #[test]
     fn unknown() {
@@ -184,6 +444,8 @@ mod tests {
         assert_eq!(None, info.edition());
         assert_eq!(None, info.codename());
         assert_eq!(Bitness::Unknown, info.bitness());
+        assert_eq!(None, info.kernel());
+        assert_eq!(None, info.os_release());
     }
 
     #[test]
@@ -229,6 +491,34 @@ mod tests {
         assert_eq!(Info::default(), Info::unknown());
     }
 
+    #[test]
+    fn family() {
+        let data = [
+            (Info::unknown(), Family::Unknown),
+            (Info::with_type(Type::Pop), Family::Debian),
+            (
+                Info {
+                    os_type: Type::Linux,
+                    os_release: Some(OsRelease::parse("ID=rocky\nID_LIKE=\"rhel centos fedora\"")),
+                    ..Default::default()
+                },
+                Family::RedHat,
+            ),
+            (
+                Info {
+                    os_type: Type::Linux,
+                    os_release: Some(OsRelease::parse("ID=unknown")),
+                    ..Default::default()
+                },
+                Family::Unknown,
+            ),
+        ];
+
+        for (info, expected) in &data {
+            assert_eq!(info.family(), *expected);
+        }
+    }
+
     #[test]
     fn display() {
         let data = [
@@ -298,6 +588,7 @@ mod tests {
                     edition: Some("edition".to_owned()),
                     codename: Some("codename".to_owned()),
                     bitness: Bitness::X64,
+                    ..Default::default()
                 },
                 "Mac OS 10.2.0 (edition) (codename) [64-bit]",
             ),
@@ -307,4 +598,106 @@ mod tests {
             assert_eq!(expected, &info.to_string());
         }
     }
+
+    #[test]
+    fn formats() {
+        let full = Info {
+            os_type: Type::Ubuntu,
+            version: Version::Semantic(18, 10, 0),
+            edition: Some("Server".to_owned()),
+            codename: Some("cosmic".to_owned()),
+            bitness: Bitness::X64,
+            architecture: Architecture::X86_64,
+            kernel: Some(KernelInfo {
+                sysname: "Linux".to_owned(),
+                release: "4.18.0-25-generic".to_owned(),
+                ..Default::default()
+            }),
+            os_release: Some(OsRelease::parse("PRETTY_NAME=\"Ubuntu 18.10\"")),
+            ..Default::default()
+        };
+        let minimal = Info::with_type(Type::Ubuntu);
+        let without_pretty_name = Info {
+            os_release: Some(OsRelease::parse("NAME=Ubuntu")),
+            ..full.clone()
+        };
+
+        let data = [
+            (&full, Format::Short, "Ubuntu 18.10.0"),
+            (
+                &full,
+                Format::Standard,
+                "Ubuntu 18.10.0 (Server) (cosmic) [64-bit]",
+            ),
+            (&full, Format::Pretty, "Ubuntu 18.10"),
+            (
+                &full,
+                Format::Long,
+                "Ubuntu 18.10.0 (Server) (cosmic) [64-bit], x86_64, Linux 4.18.0-25-generic",
+            ),
+            (
+                &without_pretty_name,
+                Format::Pretty,
+                "Ubuntu 18.10.0 (Server) (cosmic)",
+            ),
+            (&minimal, Format::Short, "Ubuntu"),
+            (&minimal, Format::Pretty, "Ubuntu"),
+            (&minimal, Format::Long, "Ubuntu [unknown bitness]"),
+        ];
+
+        for (info, format, expected) in &data {
+            assert_eq!(*expected, info.display(*format).to_string(), "{:?}", format);
+        }
+        assert_eq!(
+            format!("{:#}", full),
+            full.display(Format::Long).to_string()
+        );
+        assert_eq!(
+            format!("{}", full),
+            full.display(Format::Standard).to_string()
+        );
+    }
+
+    #[test]
+    fn comparison_ignores_trace() {
+        let info = Info::with_type(Type::Mint);
+        let mut traced = info.clone();
+        traced
+            .provenance
+            .record(Source::OsRelease, "/etc/os-release", SourceStatus::Parsed);
+        traced.upstream = Some(Box::new(Info::with_type(Type::Ubuntu)));
+
+        assert_eq!(info, traced);
+        assert_eq!(info.cmp(&traced), Ordering::Equal);
+        assert_eq!(hash(&info), hash(&traced));
+        assert_ne!(info, Info::with_type(Type::Ubuntu));
+    }
+
+    fn hash(info: &Info) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        info.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn schema_properties() {
+        let schema = serde_json::to_value(schemars::schema_for!(Info)).unwrap();
+        let mut properties: Vec<_> = schema["properties"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect();
+        properties.sort_unstable();
+
+        let info = serde_json::to_value(crate::get()).unwrap();
+        let mut fields: Vec<_> = info.as_object().unwrap().keys().cloned().collect();
+        fields.sort_unstable();
+        assert_eq!(properties, fields);
+
+        for name in &["Type", "Version", "Bitness", "Architecture", "KernelInfo"] {
+            assert!(schema["$defs"].get(name).is_some(), "{}", name);
+        }
+    }
 }
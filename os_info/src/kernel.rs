@@ -0,0 +1,340 @@
+// spell-checker:ignore sysname, rpt, rpi, deb, microsoft
+
+use std::fmt::{self, Display, Formatter};
+
+use super::Version;
+
+/// Information about the running kernel, as reported by the `uname` system call.
+///
+/// # Examples
+///
+/// ```
+/// use os_info;
+///
+/// let info = os_info::get();
+/// if let Some(kernel) = info.kernel() {
+///     println!("Kernel: {} {}", kernel.sysname(), kernel.release());
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct KernelInfo {
+    /// Name of the kernel, for example, `Linux` or `Darwin`.
+    pub(crate) sysname: String,
+    /// Kernel release, for example, `5.15.0-91-generic`.
+    pub(crate) release: String,
+    /// Kernel version (build) string.
+    pub(crate) version: String,
+    /// Hardware identifier, for example, `x86_64`.
+    pub(crate) machine: String,
+}
+
+impl KernelInfo {
+    /// Constructs kernel information from the name, release, version and machine reported by
+    /// `uname`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::KernelInfo;
+    ///
+    /// let kernel = KernelInfo::new("Linux", "5.15.0-91-generic", "#101-Ubuntu SMP", "x86_64");
+    /// assert_eq!("Linux 5.15.0-91-generic", kernel.to_string());
+    /// ```
+    pub fn new<S, R, V, M>(sysname: S, release: R, version: V, machine: M) -> Self
+    where
+        S: Into<String>,
+        R: Into<String>,
+        V: Into<String>,
+        M: Into<String>,
+    {
+        Self {
+            sysname: sysname.into(),
+            release: release.into(),
+            version: version.into(),
+            machine: machine.into(),
+        }
+    }
+
+    /// Returns the name of the kernel (`uname -s`), for example, `Linux`, `Darwin` or `FreeBSD`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info;
+    ///
+    /// if let Some(kernel) = os_info::get().kernel() {
+    ///     assert!(!kernel.sysname().is_empty());
+    /// }
+    /// ```
+    pub fn sysname(&self) -> &str {
+        &self.sysname
+    }
+
+    /// Returns the kernel release string (`uname -r`), for example, `5.15.0-91-generic`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info;
+    ///
+    /// if let Some(kernel) = os_info::get().kernel() {
+    ///     println!("Kernel release: {}", kernel.release());
+    /// }
+    /// ```
+    pub fn release(&self) -> &str {
+        &self.release
+    }
+
+    /// Returns the kernel version (build) string (`uname -v`), for example,
+    /// `#101-Ubuntu SMP Tue Nov 14 13:30:08 UTC 2023`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info;
+    ///
+    /// if let Some(kernel) = os_info::get().kernel() {
+    ///     println!("Kernel version: {}", kernel.version());
+    /// }
+    /// ```
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// Returns the hardware identifier (`uname -m`), for example, `x86_64` or `arm64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info;
+    ///
+    /// if let Some(kernel) = os_info::get().kernel() {
+    ///     println!("Machine: {}", kernel.machine());
+    /// }
+    /// ```
+    pub fn machine(&self) -> &str {
+        &self.machine
+    }
+
+    /// Returns the numeric part of the kernel release as a comparable version.
+    ///
+    /// Besides the dotted version itself, a numeric ABI or package number following the first
+    /// dash is included as further components, so `5.15.0-91-generic` becomes `5.15.0.91` and
+    /// `5.14.0-362.8.1.el9_3.x86_64` becomes `5.14.0.362.8.1`. `Version::Custom` is returned if
+    /// the release doesn't start with a number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{KernelInfo, Version};
+    ///
+    /// let kernel = KernelInfo::default();
+    /// assert_eq!(Version::Unknown, kernel.release_version());
+    /// ```
+    pub fn release_version(&self) -> Version {
+        match parse_release(&self.release) {
+            Some((components, _)) => Version::Extended(components, None),
+            None if self.release.is_empty() => Version::Unknown,
+            None => Version::Custom(self.release.clone()),
+        }
+    }
+
+    /// Returns the flavor (local suffix) of the kernel release, for example, `generic` for
+    /// `5.15.0-91-generic` or `rt5-amd64` for `6.1.0-rt5-amd64`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::KernelInfo;
+    ///
+    /// let kernel = KernelInfo::default();
+    /// assert_eq!(None, kernel.flavor());
+    /// ```
+    pub fn flavor(&self) -> Option<&str> {
+        parse_release(&self.release).and_then(|(_, flavor)| flavor)
+    }
+}
+
+impl Display for KernelInfo {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} {}", self.sysname, self.release)
+    }
+}
+
+/// Kind of the kernel an operating system type is built on. See `Type::kernel`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[allow(clippy::upper_case_acronyms)]
+#[non_exhaustive]
+pub enum Kernel {
+    /// Linux, used by Linux distributions and Android.
+    Linux,
+    /// A kernel of the BSD family, for example, FreeBSD or OpenBSD.
+    BSD,
+    /// XNU, the kernel of macOS.
+    XNU,
+    /// Windows NT.
+    NT,
+    /// The Redox microkernel.
+    Redox,
+    /// No kernel of its own, the code runs in a web browser or a JavaScript engine (Emscripten).
+    Web,
+    /// Unknown kernel.
+    #[default]
+    Unknown,
+}
+
+impl Display for Kernel {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+/// Splits a kernel release into the numeric components and the remaining flavor.
+fn parse_release(release: &str) -> Option<(Vec<u64>, Option<&str>)> {
+    let (mut components, rest) = leading_components(release)?;
+
+    let rest = match rest.strip_prefix('-') {
+        Some(abi) => match leading_components(abi) {
+            // The ABI number must be followed by a separator, so that `rt5` isn't split up.
+            Some((abi_components, tail))
+                if tail.is_empty() || tail.starts_with(['-', '.', '+', '_', '~']) =>
+            {
+                components.extend(abi_components);
+                tail
+            }
+            _ => rest,
+        },
+        None => rest,
+    };
+
+    let flavor = rest.trim_start_matches(['-', '.', '+', '_', '~']);
+    Some((components, Some(flavor).filter(|f| !f.is_empty())))
+}
+
+/// Parses leading dot-separated numbers, returning them and the rest of the string.
+fn leading_components(s: &str) -> Option<(Vec<u64>, &str)> {
+    let mut components = Vec::new();
+    let mut rest = s;
+    loop {
+        let end = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if end == 0 {
+            break;
+        }
+        components.push(rest[..end].parse().ok()?);
+        rest = &rest[end..];
+        match rest.strip_prefix('.') {
+            Some(next) if next.starts_with(|c: char| c.is_ascii_digit()) => rest = next,
+            _ => break,
+        }
+    }
+
+    if components.is_empty() {
+        None
+    } else {
+        Some((components, rest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn kernel(release: &str) -> KernelInfo {
+        KernelInfo {
+            sysname: "Linux".to_owned(),
+            release: release.to_owned(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn release_version() {
+        let data = [
+            ("5.15.0-91-generic", vec![5, 15, 0, 91], Some("generic")),
+            ("6.1.0-rt5-amd64", vec![6, 1, 0], Some("rt5-amd64")),
+            ("6.1.0-17-amd64", vec![6, 1, 0, 17], Some("amd64")),
+            (
+                "5.14.0-362.8.1.el9_3.x86_64",
+                vec![5, 14, 0, 362, 8, 1],
+                Some("el9_3.x86_64"),
+            ),
+            (
+                "5.10.0-0.deb10.16-amd64",
+                vec![5, 10, 0, 0],
+                Some("deb10.16-amd64"),
+            ),
+            (
+                "5.15.133.1-microsoft-standard-WSL2",
+                vec![5, 15, 133, 1],
+                Some("microsoft-standard-WSL2"),
+            ),
+            ("6.6.31+rpt-rpi-v8", vec![6, 6, 31], Some("rpt-rpi-v8")),
+            ("6.7.0-arch3-1", vec![6, 7, 0], Some("arch3-1")),
+            ("6.5.0-1", vec![6, 5, 0, 1], None),
+            ("13.2-RELEASE-p4", vec![13, 2], Some("RELEASE-p4")),
+            ("23.1.0", vec![23, 1, 0], None),
+            ("4.19.0+", vec![4, 19, 0], None),
+        ];
+
+        for (release, components, flavor) in &data {
+            let kernel = kernel(release);
+            assert_eq!(
+                kernel.release_version(),
+                Version::Extended(components.clone(), None),
+                "{}",
+                release
+            );
+            assert_eq!(kernel.flavor(), *flavor, "{}", release);
+        }
+    }
+
+    #[test]
+    fn unparseable_release() {
+        assert_eq!(kernel("").release_version(), Version::Unknown);
+        assert_eq!(
+            kernel("unknown").release_version(),
+            Version::Custom("unknown".to_owned())
+        );
+        assert_eq!(kernel("unknown").flavor(), None);
+    }
+
+    #[test]
+    fn ordering() {
+        assert!(kernel("5.15.0-91-generic").release_version() > Version::Semantic(5, 15, 0));
+        assert!(
+            kernel("5.15.0-91-generic").release_version()
+                < kernel("5.15.0-101-generic").release_version()
+        );
+        assert!(
+            kernel("5.4.0-150-generic").release_version()
+                < kernel("5.15.0-91-generic").release_version()
+        );
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(kernel("6.1.0-17-amd64").to_string(), "Linux 6.1.0-17-amd64");
+    }
+
+    #[test]
+    fn kernel_display() {
+        let data = [
+            (Kernel::Linux, "Linux"),
+            (Kernel::BSD, "BSD"),
+            (Kernel::XNU, "XNU"),
+            (Kernel::NT, "NT"),
+            (Kernel::Unknown, "Unknown"),
+        ];
+
+        for (kernel, expected) in &data {
+            assert_eq!(kernel.to_string(), *expected);
+        }
+    }
+}
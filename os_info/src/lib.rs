@@ -65,21 +65,90 @@ mod imp;
 #[path = "unknown/mod.rs"]
 mod imp;
 
+mod architecture;
+#[cfg(target_os = "linux")]
+mod auxv;
 mod bitness;
+mod builder;
+mod cache;
+mod codename;
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "linux",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod command;
+mod custom_type;
+mod detector;
+mod error;
+mod family;
+mod format;
+#[cfg(feature = "serde")]
+pub mod friendly;
+#[cfg(feature = "async")]
+mod future;
 mod info;
+mod kernel;
+mod lifecycle;
 #[cfg(not(windows))]
 mod matcher;
+mod os_release;
 mod os_type;
+mod provenance;
+mod registry;
+mod requirement;
+#[cfg(any(
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "macos",
+    target_os = "netbsd",
+    target_os = "openbsd"
+))]
+mod sysctl;
+pub mod testing;
 #[cfg(any(
+    target_os = "android",
     target_os = "dragonfly",
     target_os = "freebsd",
+    target_os = "linux",
+    target_os = "macos",
     target_os = "netbsd",
     target_os = "openbsd"
 ))]
 mod uname;
+mod upstream;
 mod version;
 
-pub use crate::{bitness::Bitness, info::Info, os_type::Type, version::Version};
+#[cfg(target_os = "linux")]
+use std::path::Path;
+
+pub use crate::{
+    architecture::Architecture,
+    bitness::Bitness,
+    builder::{InfoBuildError, InfoBuilder},
+    cache::{get_cached, refresh},
+    codename::{lookup_codename, lookup_release},
+    custom_type::CustomType,
+    detector::Detector,
+    error::DetectionError,
+    family::Family,
+    format::Format,
+    info::Info,
+    kernel::{Kernel, KernelInfo},
+    lifecycle::{add_lifecycle_data, load_lifecycle_data, Date, Lifecycle, LifecycleDataError},
+    os_release::OsRelease,
+    os_type::{Type, TypeParseError},
+    provenance::{Confidence, Provenance, Source, SourceRecord, SourceStatus},
+    registry::Distribution,
+    requirement::{Constraint, Operator, Requirement, RequirementParseError},
+    version::Version,
+};
+
+#[cfg(feature = "async")]
+pub use crate::future::get_async;
 
 /// Returns information about the current operating system (type, version, edition, etc.).
 ///
@@ -101,5 +170,46 @@ pub use crate::{bitness::Bitness, info::Info, os_type::Type, version::Version};
 /// println!("Bitness: {}", info.bitness());
 /// ```
 pub fn get() -> Info {
-    imp::current_platform()
+    Detector::default().detect()
+}
+
+/// Returns information about the current operating system or an error if it couldn't be
+/// detected.
+///
+/// Unlike `get`, which falls back to a generic result (for example, `Type::Linux` without a
+/// version), this function fails if none of the sources identified the operating system. The
+/// error of the most relevant source is returned. Detection never panics.
+///
+/// # Examples
+///
+/// ```
+/// use os_info;
+///
+/// match os_info::try_get() {
+///     Ok(info) => println!("OS information: {}", info),
+///     Err(e) => println!("Unable to detect the operating system: {}", e),
+/// }
+/// ```
+pub fn try_get() -> Result<Info, DetectionError> {
+    Detector::default().try_detect()
+}
+
+/// Returns information about the operating system installed under the given root directory, such
+/// as a chroot, a mounted disk image or an unpacked container filesystem.
+///
+/// Release files are read relative to `root` and no commands are executed, so the host system
+/// doesn't affect the result. Bitness is determined from the ELF headers of binaries found under
+/// the root directory.
+///
+/// # Examples
+///
+/// ```
+/// use os_info;
+///
+/// let info = os_info::get_from_root("/");
+/// println!("OS information: {}", info);
+/// ```
+#[cfg(target_os = "linux")]
+pub fn get_from_root<P: AsRef<Path>>(root: P) -> Info {
+    Detector::default().root(root).detect()
 }
@@ -0,0 +1,579 @@
+// spell-checker:ignore amzn, rhel, sles
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::Path,
+    sync::{OnceLock, PoisonError, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use log::{error, trace};
+
+use super::{Info, Type, Version};
+
+/// Lifecycle data embedded in the crate.
+const BUILT_IN: &str = include_str!("lifecycle.txt");
+
+/// Entries of the built-in data, parsed on first use.
+static BUILT_IN_ENTRIES: OnceLock<Vec<Entry>> = OnceLock::new();
+
+/// Lifecycle data loaded at runtime, in the order of loading.
+static LOADED: RwLock<Vec<Entry>> = RwLock::new(Vec::new());
+
+/// A calendar date.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::Date;
+///
+/// let date = Date::new(2022, 4, 21).unwrap();
+/// assert_eq!("2022-04-21", date.to_string());
+/// assert!(Date::new(2022, 2, 30).is_none());
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Constructs a date from the year, month (1-12) and day of the month, or returns `None` if
+    /// there is no such date.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Date;
+    ///
+    /// assert!(Date::new(2024, 2, 29).is_some());
+    /// assert!(Date::new(2023, 2, 29).is_none());
+    /// ```
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Self> {
+        if (1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day) {
+            Some(Self { year, month, day })
+        } else {
+            None
+        }
+    }
+
+    /// Returns the current date in UTC according to the system clock.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Date;
+    ///
+    /// assert!(Date::today() > Date::new(2020, 1, 1).unwrap());
+    /// ```
+    pub fn today() -> Self {
+        let days = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() / 86_400)
+            .unwrap_or(0);
+        Self::from_days(days)
+    }
+
+    /// Returns the year.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Date;
+    ///
+    /// assert_eq!(2022, Date::new(2022, 4, 21).unwrap().year());
+    /// ```
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// Returns the month, starting from 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Date;
+    ///
+    /// assert_eq!(4, Date::new(2022, 4, 21).unwrap().month());
+    /// ```
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    /// Returns the day of the month, starting from 1.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Date;
+    ///
+    /// assert_eq!(21, Date::new(2022, 4, 21).unwrap().day());
+    /// ```
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Converts the number of days since 1970-01-01 to a date of the proleptic Gregorian calendar.
+    fn from_days(days: u64) -> Self {
+        // See http://howardhinnant.github.io/date_algorithms.html#civil_from_days.
+        let z = days + 719_468;
+        let era = z / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = doy - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = yoe + era * 400 + u64::from(month <= 2);
+        Self {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        Self::new(year, month, day)
+    }
+}
+
+impl Display for Date {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// Release and end-of-support dates of an operating system version.
+///
+/// The dates come from a table embedded in the crate, which can be supplemented or overridden with
+/// `load_lifecycle_data`. A date is `None` if it's unknown or not applicable, for example, for a
+/// version without an extended support program.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::{Date, Info, Type};
+///
+/// let info = Info::with_type(Type::Ubuntu);
+/// assert_eq!(None, info.lifecycle());
+///
+/// if let Some(lifecycle) = os_info::get().lifecycle() {
+///     println!("Supported: {}", lifecycle.is_supported_on(Date::today()));
+/// }
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Lifecycle {
+    release: Option<Date>,
+    end_of_support: Option<Date>,
+    end_of_extended_support: Option<Date>,
+}
+
+impl Lifecycle {
+    /// Returns the date when the version was released.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// if let Some(lifecycle) = os_info::get().lifecycle() {
+    ///     println!("Released: {:?}", lifecycle.release());
+    /// }
+    /// ```
+    pub fn release(&self) -> Option<Date> {
+        self.release
+    }
+
+    /// Returns the date when the standard support (security updates and bug fixes available to all
+    /// users) ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// if let Some(lifecycle) = os_info::get().lifecycle() {
+    ///     println!("End of support: {:?}", lifecycle.end_of_support());
+    /// }
+    /// ```
+    pub fn end_of_support(&self) -> Option<Date> {
+        self.end_of_support
+    }
+
+    /// Returns the date when the extended support (such as Ubuntu ESM, Debian LTS, SUSE LTSS or
+    /// Windows ESU) ends.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// if let Some(lifecycle) = os_info::get().lifecycle() {
+    ///     println!("End of extended support: {:?}", lifecycle.end_of_extended_support());
+    /// }
+    /// ```
+    pub fn end_of_extended_support(&self) -> Option<Date> {
+        self.end_of_extended_support
+    }
+
+    /// Returns `true` if the version receives standard or extended support on the given date. A
+    /// version without known end dates is considered supported.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Date;
+    ///
+    /// if let Some(lifecycle) = os_info::get().lifecycle() {
+    ///     println!("Supported: {}", lifecycle.is_supported_on(Date::today()));
+    /// }
+    /// ```
+    pub fn is_supported_on(&self, date: Date) -> bool {
+        self.end_of_extended_support
+            .or(self.end_of_support)
+            .map_or(true, |end| date < end)
+    }
+}
+
+/// An error which can be returned when loading lifecycle data.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum LifecycleDataError {
+    /// The file can't be read.
+    Io(io::Error),
+    /// A line of the data is malformed.
+    Invalid {
+        /// Number of the line, starting from 1.
+        line: usize,
+        /// Description of what is wrong.
+        message: String,
+    },
+}
+
+impl Display for LifecycleDataError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            LifecycleDataError::Io(e) => write!(f, "unable to read lifecycle data: {}", e),
+            LifecycleDataError::Invalid { line, message } => {
+                write!(f, "{} on line {}", message, line)
+            }
+        }
+    }
+}
+
+impl Error for LifecycleDataError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LifecycleDataError::Io(e) => Some(e),
+            LifecycleDataError::Invalid { .. } => None,
+        }
+    }
+}
+
+/// Loads lifecycle data from a file, for example, to add versions released after this crate.
+///
+/// Each non-empty line that doesn't start with `#` describes one version: the type identifier (see
+/// `Type::id`), the version prefix, the release date, the end of standard support and, optionally,
+/// the end of extended support, separated by whitespace. Dates are written as `YYYY-MM-DD`, and
+/// `-` stands for an unknown date. The most specific version prefix matching the detected version
+/// is used, and the loaded data takes precedence over the built-in data and over the data loaded
+/// earlier. Custom types must be registered before the data referring to them is loaded.
+///
+/// Nothing is loaded if the file contains an error.
+///
+/// # Examples
+///
+/// ```no_run
+/// os_info::load_lifecycle_data("/etc/my-app/lifecycle.txt").unwrap();
+/// ```
+pub fn load_lifecycle_data<P: AsRef<Path>>(path: P) -> Result<(), LifecycleDataError> {
+    let data = fs::read_to_string(path).map_err(LifecycleDataError::Io)?;
+    add_lifecycle_data(&data)
+}
+
+/// Adds lifecycle data in the format described in `load_lifecycle_data`.
+///
+/// # Examples
+///
+/// ```
+/// os_info::add_lifecycle_data("ubuntu 26.04 2026-04-23 2031-05-29 2036-04-23").unwrap();
+/// ```
+pub fn add_lifecycle_data(data: &str) -> Result<(), LifecycleDataError> {
+    let entries = parse(data)?;
+    trace!("Adding {} lifecycle entries", entries.len());
+    LOADED
+        .write()
+        .unwrap_or_else(PoisonError::into_inner)
+        .extend(entries);
+    Ok(())
+}
+
+/// Returns the lifecycle of the detected operating system version.
+pub(crate) fn find(info: &Info) -> Option<Lifecycle> {
+    let os_type = match info.os_type() {
+        // Red Hat Enterprise Linux detected from `/etc/redhat-release` is reported as `Redhat`.
+        Type::Redhat => Type::RedHatEnterprise,
+        // The built-in data describes only client editions.
+        Type::Windows if info.edition().is_some_and(|e| e.contains("Server")) => return None,
+        os_type => os_type,
+    };
+    find_version(os_type, info.version())
+}
+
+fn find_version(os_type: Type, version: &Version) -> Option<Lifecycle> {
+    let components = version.components();
+    if components.is_empty() {
+        return None;
+    }
+
+    let loaded = LOADED.read().unwrap_or_else(PoisonError::into_inner);
+    loaded
+        .iter()
+        .rev()
+        .chain(built_in())
+        .filter(|e| e.os_type == os_type && components.starts_with(&e.version))
+        // Keep the first of the equally specific entries.
+        .fold(None, |best: Option<&Entry>, e| match best {
+            Some(best) if best.version.len() >= e.version.len() => Some(best),
+            _ => Some(e),
+        })
+        .map(|e| e.lifecycle)
+}
+
+/// Returns the built-in entries. The data is checked by the tests, so an error is only logged.
+fn built_in() -> &'static [Entry] {
+    BUILT_IN_ENTRIES.get_or_init(|| {
+        parse(BUILT_IN).unwrap_or_else(|e| {
+            error!("Invalid built-in lifecycle data: {}", e);
+            Vec::new()
+        })
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    os_type: Type,
+    version: Vec<u64>,
+    lifecycle: Lifecycle,
+}
+
+fn parse(data: &str) -> Result<Vec<Entry>, LifecycleDataError> {
+    data.lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+        .map(|(line, content)| {
+            parse_entry(content).map_err(|message| LifecycleDataError::Invalid { line, message })
+        })
+        .collect()
+}
+
+fn parse_entry(line: &str) -> Result<Entry, String> {
+    let fields: Vec<_> = line.split_whitespace().collect();
+    if !(4..=5).contains(&fields.len()) {
+        return Err(format!("expected 4 or 5 fields, found {}", fields.len()));
+    }
+
+    let os_type = fields[0].parse::<Type>().map_err(|e| e.to_string())?;
+    let version = fields[1]
+        .split('.')
+        .map(str::parse)
+        .collect::<Result<Vec<u64>, _>>()
+        .map_err(|_| format!("invalid version '{}'", fields[1]))?;
+    let date = |index: usize| match fields.get(index) {
+        None | Some(&"-") => Ok(None),
+        Some(s) => Date::parse(s)
+            .map(Some)
+            .ok_or_else(|| format!("invalid date '{}'", s)),
+    };
+
+    Ok(Entry {
+        os_type,
+        version,
+        lifecycle: Lifecycle {
+            release: date(2)?,
+            end_of_support: date(3)?,
+            end_of_extended_support: date(4)?,
+        },
+    })
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn date(year: u16, month: u8, day: u8) -> Date {
+        Date::new(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn built_in() {
+        let entries = parse(BUILT_IN).unwrap();
+        assert_eq!(super::built_in(), entries.as_slice());
+        for entry in &entries {
+            let lifecycle = entry.lifecycle;
+            assert!(lifecycle.release.is_some(), "{:?}", entry);
+            if let Some(end) = lifecycle.end_of_support {
+                assert!(lifecycle.release < Some(end), "{:?}", entry);
+            }
+            if let Some(extended) = lifecycle.end_of_extended_support {
+                assert!(Some(extended) > lifecycle.end_of_support, "{:?}", entry);
+            }
+        }
+    }
+
+    #[test]
+    fn find_versions() {
+        let data = [
+            (Type::Ubuntu, Version::Semantic(22, 4, 0), Some(2022)),
+            (
+                Type::Ubuntu,
+                Version::Custom("22.04".to_owned()),
+                Some(2022),
+            ),
+            (Type::Ubuntu, Version::Semantic(22, 10, 0), None),
+            (Type::Debian, Version::Semantic(12, 5, 0), Some(2023)),
+            (
+                Type::RedHatEnterprise,
+                Version::Semantic(8, 6, 0),
+                Some(2019),
+            ),
+            (Type::Macos, Version::Semantic(14, 2, 1), Some(2023)),
+            (Type::Macos, Version::Semantic(10, 15, 7), Some(2019)),
+            (Type::Amazon, Version::Semantic(2, 0, 0), Some(2018)),
+            (Type::Amazon, Version::Semantic(2023, 0, 0), Some(2023)),
+            (Type::SUSE, Version::Semantic(15, 5, 0), Some(2023)),
+            (
+                Type::Windows,
+                Version::Extended(vec![10, 0, 19045, 3930], None),
+                Some(2022),
+            ),
+            (Type::Windows, Version::Semantic(10, 0, 17763), None),
+            (Type::Arch, Version::Rolling(None), None),
+            (Type::Fedora, Version::Unknown, None),
+        ];
+
+        for (os_type, version, year) in &data {
+            let release_year = find_version(*os_type, version)
+                .and_then(|l| l.release())
+                .map(|d| d.year());
+            assert_eq!(release_year, *year, "{} {}", os_type, version);
+        }
+    }
+
+    #[test]
+    fn find_info() {
+        let info = Info {
+            version: Version::Semantic(7, 9, 0),
+            ..Info::with_type(Type::Redhat)
+        };
+        assert_eq!(
+            find(&info).and_then(|l| l.end_of_support()),
+            Some(date(2024, 6, 30))
+        );
+
+        let info = Info {
+            version: Version::Semantic(10, 0, 19045),
+            edition: Some("Windows Server 2022 Datacenter".to_owned()),
+            ..Info::with_type(Type::Windows)
+        };
+        assert_eq!(find(&info), None);
+    }
+
+    #[test]
+    fn loaded_precedence() {
+        // Loaded data is shared by all tests, so a type without built-in data is used.
+        add_lifecycle_data("pop 22.04 2022-04-25 2027-04-25\npop 22 2022-01-01 -").unwrap();
+        add_lifecycle_data("# Newer data\npop 22.04 2022-04-25 2027-06-01 2032-04-09").unwrap();
+
+        let lifecycle = find_version(Type::Pop, &Version::Semantic(22, 4, 0)).unwrap();
+        assert_eq!(lifecycle.end_of_support(), Some(date(2027, 6, 1)));
+        assert_eq!(lifecycle.end_of_extended_support(), Some(date(2032, 4, 9)));
+
+        let lifecycle = find_version(Type::Pop, &Version::Semantic(22, 10, 0)).unwrap();
+        assert_eq!(lifecycle.release(), Some(date(2022, 1, 1)));
+        assert_eq!(lifecycle.end_of_support(), None);
+    }
+
+    #[test]
+    fn invalid_data() {
+        let data = [
+            ("ubuntu 22.04", 1, "expected 4 or 5 fields, found 2"),
+            (
+                "\n# Comment\nnope 1 - -",
+                3,
+                "unknown operating system type 'nope'",
+            ),
+            ("debian 12.x - -", 1, "invalid version '12.x'"),
+            ("debian 12 2023-02-30 -", 1, "invalid date '2023-02-30'"),
+        ];
+
+        for (content, line, message) in &data {
+            match add_lifecycle_data(content) {
+                Err(LifecycleDataError::Invalid {
+                    line: l,
+                    message: m,
+                }) => {
+                    assert_eq!((l, m.as_str()), (*line, *message));
+                }
+                result => panic!("Unexpected result for {:?}: {:?}", content, result),
+            }
+        }
+    }
+
+    #[test]
+    fn missing_file() {
+        let result = load_lifecycle_data("/nonexistent/lifecycle.txt");
+        assert!(matches!(result, Err(LifecycleDataError::Io(_))));
+    }
+
+    #[test]
+    fn supported() {
+        let lifecycle = Lifecycle {
+            release: Some(date(2020, 4, 23)),
+            end_of_support: Some(date(2025, 5, 29)),
+            end_of_extended_support: Some(date(2030, 4, 2)),
+        };
+        assert!(lifecycle.is_supported_on(date(2027, 1, 1)));
+        assert!(!lifecycle.is_supported_on(date(2030, 4, 2)));
+
+        let lifecycle = Lifecycle {
+            end_of_extended_support: None,
+            ..lifecycle
+        };
+        assert!(!lifecycle.is_supported_on(date(2027, 1, 1)));
+        assert!(Lifecycle {
+            end_of_support: None,
+            ..lifecycle
+        }
+        .is_supported_on(date(2100, 1, 1)));
+    }
+
+    #[test]
+    fn dates() {
+        let data = [
+            (0, date(1970, 1, 1)),
+            (11_016, date(2000, 2, 29)),
+            (19_466, date(2023, 4, 19)),
+            (20_088, date(2024, 12, 31)),
+        ];
+
+        for (days, expected) in &data {
+            assert_eq!(Date::from_days(*days), *expected);
+        }
+        assert_eq!(Date::parse("2024-02-29"), Some(date(2024, 2, 29)));
+        assert_eq!(Date::parse("2024-13-01"), None);
+        assert_eq!(date(2024, 1, 5).to_string(), "2024-01-05");
+    }
+}
@@ -1,40 +1,99 @@
-// spell-checker:ignore sles
+// spell-checker:ignore sled, sles
 
-use std::{fs::File, io::Read, path::Path};
+use std::{
+    fs::{self, File},
+    io::{ErrorKind, Read},
+    path::{Path, PathBuf},
+};
 
 use log::{trace, warn};
 
-use crate::{matcher::Matcher, Bitness, Info, Type, Version};
+use super::rootfs;
+use crate::{
+    matcher::Matcher, registry, Bitness, DetectionError, Info, OsRelease, Provenance, Source,
+    SourceStatus, Type, Version,
+};
+
+/// Checks the release files of the given kinds (`Source::ReleaseFile` and `Source::OsRelease`)
+/// under the root directory, in the order of the kinds and then in the order of precedence.
+pub fn get_from_root(root: &Path, sources: &[Source], provenance: &mut Provenance) -> Option<Info> {
+    let distributions: Vec<_> = sources
+        .iter()
+        .flat_map(|&s| DISTRIBUTIONS.iter().filter(move |d| source(d.path) == s))
+        .cloned()
+        .collect();
+    retrieve(&distributions, root, provenance)
+}
+
+/// Reads the `os-release` file under the given root directory, falling back to
+/// `/usr/lib/os-release` if `/etc/os-release` doesn't exist, as the specification requires.
+pub fn os_release(root: &Path) -> Option<OsRelease> {
+    os_release_with_path(root).map(|(_, os_release)| os_release)
+}
 
-pub fn get() -> Option<Info> {
-    retrieve(&DISTRIBUTIONS)
+/// Same as `os_release`, but also returns the path of the file that was read.
+pub fn os_release_with_path(root: &Path) -> Option<(PathBuf, OsRelease)> {
+    OS_RELEASE_PATHS.iter().find_map(|path| {
+        let path = rootfs::resolve(root, Path::new(path));
+        match fs::read_to_string(&path) {
+            Ok(content) => Some((path, OsRelease::parse(&content))),
+            Err(e) => {
+                trace!("Unable to read {:?} file: {:?}", path, e);
+                None
+            }
+        }
+    })
 }
 
-fn retrieve(distributions: &[ReleaseInfo]) -> Option<Info> {
+fn retrieve(
+    distributions: &[ReleaseInfo],
+    root: &Path,
+    provenance: &mut Provenance,
+) -> Option<Info> {
     for release_info in distributions {
-        if !Path::new(release_info.path).exists() {
-            trace!("Path '{}' doesn't exist", release_info.path);
+        let path = rootfs::resolve(root, Path::new(release_info.path));
+        let location = path.display().to_string();
+        let source = source(release_info.path);
+        if !path.exists() {
+            trace!("Path '{}' doesn't exist", path.display());
+            let error = DetectionError::file(path, &ErrorKind::NotFound.into());
+            provenance.record_error(source, location, error);
             continue;
         }
 
-        let mut file = match File::open(&release_info.path) {
+        let mut file = match File::open(&path) {
             Ok(val) => val,
             Err(e) => {
-                warn!("Unable to open {:?} file: {:?}", release_info.path, e);
+                warn!("Unable to open {:?} file: {:?}", path, e);
+                let error = DetectionError::file(path, &e);
+                provenance.record_error(source, location, error);
                 continue;
             }
         };
 
         let mut file_content = String::new();
         if let Err(e) = file.read_to_string(&mut file_content) {
-            warn!("Unable to read {:?} file: {:?}", release_info.path, e);
+            warn!("Unable to read {:?} file: {:?}", path, e);
+            let error = DetectionError::file(path, &e);
+            provenance.record_error(source, location, error);
             continue;
         }
 
-        let os_type = Matcher::KeyValue { key: "NAME" }
-            .find(&file_content)
-            .and_then(|name| get_type(&name))
-            .unwrap_or(release_info.os_type);
+        let os_release = OsRelease::parse(&file_content);
+        let os_type = match (registry::from_os_release(&os_release), os_release.name()) {
+            (Some(os_type), _) => {
+                provenance.record(source, location, SourceStatus::Parsed);
+                os_type
+            }
+            (None, name) => {
+                provenance.record(source, location, SourceStatus::Parsed);
+                // A distribution with an unknown name is still a Linux distribution.
+                name.and_then(get_type).unwrap_or_else(|| {
+                    trace!("Unknown NAME {:?} in {:?}", name, path);
+                    release_info.os_type
+                })
+            }
+        };
 
         let version = release_info
             .version_matcher
@@ -45,6 +104,11 @@ fn retrieve(distributions: &[ReleaseInfo]) -> Option<Info> {
         return Some(Info {
             os_type,
             version,
+            edition: variant(&os_release).or_else(|| redhat_variant(&file_content)),
+            codename: os_release
+                .version_codename()
+                .filter(|c| !c.is_empty())
+                .map(str::to_owned),
             bitness: Bitness::Unknown,
             ..Default::default()
         });
@@ -53,6 +117,76 @@ fn retrieve(distributions: &[ReleaseInfo]) -> Option<Info> {
     None
 }
 
+/// Returns the variant of the distribution (such as Workstation, Server or CoreOS) from the
+/// `VARIANT` field of the `os-release` file, from a humanized `VARIANT_ID` if the former is missing,
+/// or from the name of SUSE Linux Enterprise products, which don't have these fields.
+pub fn variant(os_release: &OsRelease) -> Option<String> {
+    os_release
+        .variant()
+        .filter(|v| !v.is_empty())
+        .map(str::to_owned)
+        .or_else(|| {
+            os_release
+                .variant_id()
+                .filter(|v| !v.is_empty())
+                .map(humanize_variant_id)
+        })
+        .or_else(|| match os_release.name() {
+            Some("SLES") => Some("Server".to_owned()),
+            Some("SLED") => Some("Desktop".to_owned()),
+            _ => None,
+        })
+}
+
+/// Turns a `VARIANT_ID` such as `kde-plasma` into a display name (`Kde Plasma`).
+fn humanize_variant_id(id: &str) -> String {
+    match id {
+        "sles-sap" => "SAP Applications".to_owned(),
+        _ => id
+            .split(['-', '_'])
+            .filter(|word| !word.is_empty())
+            .map(|word| {
+                let mut chars = word.chars();
+                chars
+                    .next()
+                    .map(|first| first.to_uppercase().chain(chars).collect())
+                    .unwrap_or_default()
+            })
+            .collect::<Vec<String>>()
+            .join(" "),
+    }
+}
+
+/// Returns the edition of Ubuntu, which doesn't report it in the `os-release` file. Ubuntu Server
+/// is recognized by the documentation of its `ubuntu-server` metapackage.
+pub fn ubuntu_edition(root: &Path) -> Option<String> {
+    let path = rootfs::resolve(root, Path::new("/usr/share/doc/ubuntu-server"));
+    if path.is_dir() {
+        Some("Server".to_owned())
+    } else {
+        None
+    }
+}
+
+/// Extracts the variant from the content of the `/etc/redhat-release` file of RHEL 7 and earlier,
+/// for example, `Server` from `Red Hat Enterprise Linux Server release 7.9 (Maipo)`.
+fn redhat_variant(content: &str) -> Option<String> {
+    let rest = content.trim().strip_prefix("Red Hat Enterprise Linux ")?;
+    let (variant, _) = rest.split_once(" release ")?;
+    if variant.is_empty() || variant.contains(' ') {
+        return None;
+    }
+    Some(variant.to_owned())
+}
+
+fn source(path: &str) -> Source {
+    if OS_RELEASE_PATHS.contains(&path) {
+        Source::OsRelease
+    } else {
+        Source::ReleaseFile
+    }
+}
+
 fn get_type(name: &str) -> Option<Type> {
     match name.to_lowercase().as_ref() {
         "alpine linux" => Some(Type::Alpine),
@@ -66,7 +200,9 @@ fn get_type(name: &str) -> Option<Type> {
         "linux mint" => Some(Type::Mint),
         "mariner" => Some(Type::Mariner),
         "nixos" => Some(Type::NixOS),
+        "oracle linux server" => Some(Type::OracleLinux),
         "red hat enterprise linux" => Some(Type::Redhat),
+        "sled" => Some(Type::SUSE),
         "sles" => Some(Type::SUSE),
         "ubuntu" => Some(Type::Ubuntu),
         _ => None,
@@ -80,19 +216,22 @@ struct ReleaseInfo<'a> {
     version_matcher: Matcher,
 }
 
+/// Locations of the `os-release` file in the order of precedence.
+const OS_RELEASE_PATHS: [&str; 2] = ["/etc/os-release", "/usr/lib/os-release"];
+
 /// List of all supported distributions and the information on how to parse their version from the
 /// release file.
-const DISTRIBUTIONS: [ReleaseInfo; 6] = [
+const DISTRIBUTIONS: [ReleaseInfo; 8] = [
     // Due to shenanigans with Oracle Linux including an /etc/redhat-release file that states
-    // that the OS is Red Hat Enterprise Linux, this /etc/os-release file MUST be checked
-    // before this code checks /etc/redhat-release. If it does not get run first,
-    // it will unintentionally report that the operating system is Red Hat Enterprise Linux
-    // instead of Oracle Linux.
+    // that the OS is Red Hat Enterprise Linux, /etc/oracle-release MUST be checked before
+    // /etc/redhat-release. Otherwise it will unintentionally report that the operating system is
+    // Red Hat Enterprise Linux instead of Oracle Linux when release files take precedence over
+    // the os-release file.
     ReleaseInfo {
         os_type: Type::Mariner,
         path: "/etc/mariner-release",
         version_matcher: Matcher::PrefixedVersion {
-            prefix: "CBL-Mariner "
+            prefix: "CBL-Mariner ",
         },
     },
     ReleaseInfo {
@@ -111,10 +250,20 @@ const DISTRIBUTIONS: [ReleaseInfo; 6] = [
         version_matcher: Matcher::AllTrimmed,
     },
     ReleaseInfo {
-        os_type: Type::OracleLinux,
+        os_type: Type::Linux,
         path: "/etc/os-release",
         version_matcher: Matcher::KeyValue { key: "VERSION_ID" },
     },
+    ReleaseInfo {
+        os_type: Type::Linux,
+        path: "/usr/lib/os-release",
+        version_matcher: Matcher::KeyValue { key: "VERSION_ID" },
+    },
+    ReleaseInfo {
+        os_type: Type::OracleLinux,
+        path: "/etc/oracle-release",
+        version_matcher: Matcher::PrefixedVersion { prefix: "release" },
+    },
     ReleaseInfo {
         os_type: Type::Redhat,
         path: "/etc/redhat-release",
@@ -127,15 +276,17 @@ mod tests {
     use super::*;
     use pretty_assertions::assert_eq;
 
+    const SOURCES: [Source; 2] = [Source::ReleaseFile, Source::OsRelease];
+
     #[test]
     fn oracle_linux() {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::OracleLinux);
         assert_eq!(info.version, Version::Semantic(8, 1, 0));
-        assert_eq!(info.edition, None);
+        assert_eq!(info.edition, Some("Server".to_owned()));
         assert_eq!(info.codename, None);
     }
 
@@ -144,7 +295,7 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-alpine-3-12";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::Alpine);
         assert_eq!(info.version, Version::Semantic(3, 12, 0));
         assert_eq!(info.edition, None);
@@ -156,7 +307,7 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-amazon-1";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::Amazon);
         assert_eq!(info.version, Version::Semantic(2018, 3, 0));
         assert_eq!(info.edition, None);
@@ -168,7 +319,7 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-amazon-2";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::Amazon);
         assert_eq!(info.version, Version::Semantic(2, 0, 0));
         assert_eq!(info.edition, None);
@@ -180,7 +331,7 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-centos";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::CentOS);
         assert_eq!(info.version, Version::Semantic(7, 0, 0));
         assert_eq!(info.edition, None);
@@ -192,7 +343,7 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-centos-stream";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::CentOS);
         assert_eq!(info.version, Version::Semantic(8, 0, 0));
         assert_eq!(info.edition, None);
@@ -204,10 +355,10 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-fedora-32";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::Fedora);
         assert_eq!(info.version, Version::Semantic(32, 0, 0));
-        assert_eq!(info.edition, None);
+        assert_eq!(info.edition, Some("Cloud Edition".to_owned()));
         assert_eq!(info.codename, None);
     }
 
@@ -216,10 +367,10 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-fedora-35";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::Fedora);
         assert_eq!(info.version, Version::Semantic(35, 0, 0));
-        assert_eq!(info.edition, None);
+        assert_eq!(info.edition, Some("Workstation Edition".to_owned()));
         assert_eq!(info.codename, None);
     }
 
@@ -228,14 +379,14 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-nixos";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::NixOS);
         assert_eq!(
             info.version,
-            Version::Custom("21.05pre275822.916ee862e87".to_string())
+            Version::Extended(vec![21, 5], Some("pre275822.916ee862e87".to_string()))
         );
         assert_eq!(info.edition, None);
-        assert_eq!(info.codename, None);
+        assert_eq!(info.codename, Some("okapi".to_owned()));
     }
 
     #[test]
@@ -243,11 +394,14 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-rhel";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::Redhat);
         assert_eq!(info.version, Version::Semantic(8, 2, 0));
         assert_eq!(info.edition, None);
         assert_eq!(info.codename, None);
+
+        let requirement: crate::Requirement = "rhel >= 8".parse().unwrap();
+        assert!(info.satisfies(&requirement));
     }
 
     #[test]
@@ -255,10 +409,10 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-suse-12";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::SUSE);
         assert_eq!(info.version, Version::Semantic(12, 5, 0));
-        assert_eq!(info.edition, None);
+        assert_eq!(info.edition, Some("Server".to_owned()));
         assert_eq!(info.codename, None);
     }
 
@@ -267,10 +421,10 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-suse-15";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::SUSE);
         assert_eq!(info.version, Version::Semantic(15, 2, 0));
-        assert_eq!(info.edition, None);
+        assert_eq!(info.edition, Some("Server".to_owned()));
         assert_eq!(info.codename, None);
     }
 
@@ -279,11 +433,11 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-ubuntu";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::Ubuntu);
         assert_eq!(info.version, Version::Semantic(18, 10, 0));
         assert_eq!(info.edition, None);
-        assert_eq!(info.codename, None);
+        assert_eq!(info.codename, Some("cosmic".to_owned()));
     }
 
     #[test]
@@ -291,11 +445,11 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[4].clone()];
         distributions[0].path = "src/linux/tests/os-release-mint";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::Mint);
         assert_eq!(info.version, Version::Semantic(20, 0, 0));
         assert_eq!(info.edition, None);
-        assert_eq!(info.codename, None);
+        assert_eq!(info.codename, Some("ulyana".to_owned()));
     }
 
     #[test]
@@ -303,7 +457,7 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[1].clone()];
         distributions[0].path = "src/linux/tests/centos-release";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::CentOS);
         assert_eq!(info.version, Version::Custom("XX".to_owned()));
         assert_eq!(info.edition, None);
@@ -315,7 +469,7 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[2].clone()];
         distributions[0].path = "src/linux/tests/fedora-release";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::Fedora);
         assert_eq!(info.version, Version::Semantic(26, 0, 0));
         assert_eq!(info.edition, None);
@@ -324,10 +478,10 @@ mod tests {
 
     #[test]
     fn redhat() {
-        let mut distributions = [DISTRIBUTIONS[5].clone()];
+        let mut distributions = [DISTRIBUTIONS[7].clone()];
         distributions[0].path = "src/linux/tests/redhat-release";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::Redhat);
         assert_eq!(info.version, Version::Custom("XX".to_owned()));
         assert_eq!(info.edition, None);
@@ -339,7 +493,7 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[3].clone()];
         distributions[0].path = "src/linux/tests/alpine-release";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::Alpine);
         assert_eq!(info.version, Version::Custom("A.B.C".to_owned()));
         assert_eq!(info.edition, None);
@@ -351,10 +505,230 @@ mod tests {
         let mut distributions = [DISTRIBUTIONS[0].clone()];
         distributions[0].path = "src/linux/tests/mariner-release";
 
-        let info = retrieve(&distributions).unwrap();
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
         assert_eq!(info.os_type(), Type::Mariner);
         assert_eq!(info.version, Version::Semantic(2, 0, 20220210));
         assert_eq!(info.edition, None);
         assert_eq!(info.codename, None);
     }
+
+    #[test]
+    fn root_directory() {
+        let mut provenance = Provenance::default();
+        let info = get_from_root(
+            Path::new("src/linux/tests/rootfs-alpine"),
+            &SOURCES,
+            &mut provenance,
+        )
+        .unwrap();
+        assert_eq!(info.os_type(), Type::Alpine);
+        assert_eq!(info.version, Version::Semantic(3, 12, 0));
+        assert_eq!(info.edition, None);
+        assert_eq!(info.codename, None);
+
+        let statuses: Vec<_> = provenance.records().iter().map(|r| r.status()).collect();
+        assert_eq!(
+            statuses,
+            [
+                SourceStatus::Missing,
+                SourceStatus::Missing,
+                SourceStatus::Missing,
+                SourceStatus::Parsed
+            ]
+        );
+        assert_eq!(
+            provenance.records()[3].location(),
+            "src/linux/tests/rootfs-alpine/etc/alpine-release"
+        );
+    }
+
+    #[test]
+    fn os_release_only() {
+        let mut provenance = Provenance::default();
+        let root = Path::new("src/linux/tests/rootfs-alpine");
+        let info = get_from_root(root, &[Source::OsRelease], &mut provenance).unwrap();
+        assert_eq!(info.os_type(), Type::Alpine);
+        assert_eq!(info.version, Version::Semantic(3, 12, 0));
+        assert_eq!(provenance.records().len(), 1);
+        assert_eq!(provenance.records()[0].source(), Source::OsRelease);
+
+        let mut provenance = Provenance::default();
+        assert_eq!(get_from_root(root, &[], &mut provenance), None);
+        assert!(provenance.records().is_empty());
+    }
+
+    #[test]
+    fn empty_root_directory() {
+        let mut provenance = Provenance::default();
+        assert_eq!(
+            get_from_root(
+                Path::new("src/linux/tests/rootfs-missing"),
+                &SOURCES,
+                &mut provenance
+            ),
+            None
+        );
+        assert_eq!(provenance.records().len(), DISTRIBUTIONS.len());
+        assert!(provenance
+            .records()
+            .iter()
+            .all(|r| r.status() == SourceStatus::Missing));
+    }
+
+    #[test]
+    fn oracle_release() {
+        let mut distributions = [DISTRIBUTIONS[6].clone()];
+        distributions[0].path = "src/linux/tests/oracle-release";
+
+        let info = retrieve(&distributions, Path::new(""), &mut Provenance::default()).unwrap();
+        assert_eq!(info.os_type(), Type::OracleLinux);
+        assert_eq!(info.version, Version::Semantic(8, 1, 0));
+    }
+
+    #[test]
+    fn source_order() {
+        let root = Path::new("src/linux/tests/rootfs-alpine");
+        let data = [
+            (
+                [Source::ReleaseFile, Source::OsRelease],
+                "src/linux/tests/rootfs-alpine/etc/alpine-release",
+            ),
+            (
+                [Source::OsRelease, Source::ReleaseFile],
+                "src/linux/tests/rootfs-alpine/usr/lib/os-release",
+            ),
+        ];
+
+        for (sources, expected) in &data {
+            let mut provenance = Provenance::default();
+            let info = get_from_root(root, sources, &mut provenance).unwrap();
+            assert_eq!(info.os_type(), Type::Alpine);
+            let last = provenance.records().last().unwrap();
+            assert_eq!(last.location(), *expected, "{:?}", sources);
+        }
+    }
+
+    #[test]
+    fn unrecognized_name() {
+        let mut distributions = [DISTRIBUTIONS[4].clone()];
+        distributions[0].path = "src/linux/tests/os-release-debian";
+        let mut provenance = Provenance::default();
+        let info = retrieve(&distributions, Path::new(""), &mut provenance).unwrap();
+        // Falls back to a generic Linux distribution.
+        assert_eq!(info.os_type(), Type::Linux);
+        assert_eq!(info.codename, Some("bullseye".to_owned()));
+        assert_eq!(provenance.records()[0].status(), SourceStatus::Parsed);
+        assert_eq!(provenance.records()[0].error(), None);
+    }
+
+    #[test]
+    fn etc_os_release_unknown_name() {
+        let mut distributions = [DISTRIBUTIONS[4].clone()];
+        distributions[0].path = "src/linux/tests/os-release-unknown";
+        let mut provenance = Provenance::default();
+        let info = retrieve(&distributions, Path::new(""), &mut provenance).unwrap();
+        assert_eq!(info.os_type(), Type::Linux);
+        assert_eq!(info.version, Version::Semantic(1, 4, 0));
+
+        provenance.select(&info);
+        assert_eq!(provenance.result(), Ok(()));
+        assert_eq!(provenance.confidence(), crate::Confidence::Low);
+    }
+
+    #[test]
+    fn variants() {
+        let data = [
+            ("VARIANT=\"CoreOS\"\nVARIANT_ID=coreos", Some("CoreOS")),
+            ("VARIANT=\"\"\nVARIANT_ID=silverblue", Some("Silverblue")),
+            ("VARIANT_ID=kde-plasma", Some("Kde Plasma")),
+            ("NAME=\"SLED\"", Some("Desktop")),
+            ("NAME=\"SLES\"", Some("Server")),
+            (
+                "NAME=\"SLES\"\nVARIANT_ID=\"sles-sap\"",
+                Some("SAP Applications"),
+            ),
+            ("NAME=\"Ubuntu\"", None),
+        ];
+
+        for (content, expected) in &data {
+            let os_release = OsRelease::parse(content);
+            assert_eq!(variant(&os_release).as_deref(), *expected, "{}", content);
+        }
+    }
+
+    #[test]
+    fn redhat_variants() {
+        let data = [
+            (
+                "Red Hat Enterprise Linux Server release 7.9 (Maipo)\n",
+                Some("Server"),
+            ),
+            (
+                "Red Hat Enterprise Linux Workstation release 6.10 (Santiago)",
+                Some("Workstation"),
+            ),
+            ("Red Hat Enterprise Linux release 8.2 (Ootpa)", None),
+            ("Redhat Linux release XX", None),
+        ];
+
+        for (content, expected) in &data {
+            assert_eq!(redhat_variant(content).as_deref(), *expected, "{}", content);
+        }
+    }
+
+    #[test]
+    fn registered_distribution() {
+        use crate::{CustomType, Distribution, Family};
+
+        const CUSTOM: CustomType = CustomType::new("example", "Example Linux", Family::RedHat);
+        Distribution::new(Type::Other(CUSTOM))
+            .name("Example Enterprise Linux")
+            .register();
+
+        let mut distributions = [DISTRIBUTIONS[4].clone()];
+        distributions[0].path = "src/linux/tests/os-release-custom";
+        let mut provenance = Provenance::default();
+        let info = retrieve(&distributions, Path::new(""), &mut provenance).unwrap();
+        assert_eq!(info.os_type(), Type::Other(CUSTOM));
+        assert_eq!(info.version, Version::Semantic(9, 2, 0));
+        assert_eq!(provenance.records()[0].status(), SourceStatus::Parsed);
+    }
+
+    #[test]
+    fn usr_lib_os_release() {
+        let root = Path::new("src/linux/tests/rootfs-fedora");
+        let info = get_from_root(root, &SOURCES, &mut Provenance::default()).unwrap();
+        assert_eq!(info.os_type(), Type::Fedora);
+        assert_eq!(info.version, Version::Semantic(35, 0, 0));
+
+        let os_release = os_release(root).unwrap();
+        assert_eq!(os_release.id(), Some("fedora"));
+        assert_eq!(os_release.variant_id(), Some("workstation"));
+    }
+
+    #[test]
+    fn usr_lib_os_release_unknown_name() {
+        let mut distributions = [DISTRIBUTIONS[5].clone()];
+        distributions[0].path = "src/linux/tests/os-release-unknown";
+        let mut provenance = Provenance::default();
+        let info = retrieve(&distributions, Path::new(""), &mut provenance).unwrap();
+        assert_eq!(info.os_type(), Type::Linux);
+        assert_eq!(info.version, Version::Semantic(1, 4, 0));
+        assert_eq!(provenance.records()[0].status(), SourceStatus::Parsed);
+    }
+
+    #[test]
+    fn etc_os_release() {
+        let os_release = os_release(Path::new("src/linux/tests/rootfs-ubuntu")).unwrap();
+        assert_eq!(os_release.id(), Some("ubuntu"));
+        assert_eq!(os_release.version_codename(), Some("cosmic"));
+    }
+
+    #[test]
+    fn missing_os_release() {
+        assert_eq!(
+            os_release(Path::new("src/linux/tests/rootfs-missing")),
+            None
+        );
+    }
 }
@@ -1,48 +1,116 @@
 // spell-checker:ignore codename, noarch, rhel, ootpa, maipo
 
-use std::process::Command;
+use std::{fs, path::Path, process::Command, time::Duration};
 
 use log::{debug, trace};
 
-use crate::{matcher::Matcher, Info, Type, Version};
+use super::{file_release, rootfs};
+use crate::{
+    command, matcher::Matcher, registry, DetectionError, Info, OsRelease, Provenance, Source,
+    SourceStatus, Type, Version,
+};
 
-pub fn get() -> Option<Info> {
-    let release = retrieve()?;
+pub fn get(timeout: Option<Duration>, provenance: &mut Provenance) -> Option<Info> {
+    retrieve(timeout, provenance).map(info)
+}
+
+/// Reads the `/etc/lsb-release` file under the given root directory instead of running the
+/// `lsb_release` command, which can only describe the host system.
+pub fn get_from_root(root: &Path, provenance: &mut Provenance) -> Option<Info> {
+    retrieve_from_file(root, provenance).map(info)
+}
+
+/// Produces the same result as the `lsb_release` command without running it: reads the
+/// `/etc/lsb-release` file and, if it doesn't exist, derives the information from the
+/// `os-release` file the way the command does. A distribution that isn't recognized this way is
+/// left to the release files.
+pub fn get_from_files(root: &Path, provenance: &mut Provenance) -> Option<Info> {
+    if let Some(release) = retrieve_from_file(root, provenance) {
+        return Some(info(release));
+    }
+
+    let (path, os_release) = file_release::os_release_with_path(root)?;
+    let location = path.display().to_string();
+    let mut info = info(from_os_release(&os_release));
+    if let Some(os_type) = registry::from_os_release(&os_release) {
+        info.os_type = os_type;
+    }
+    if info.os_type == Type::Linux {
+        let error = DetectionError::UnparseableContent {
+            location: location.clone(),
+            message: format!("unknown ID '{}'", os_release.id().unwrap_or_default()),
+        };
+        provenance.record_error(Source::LsbReleaseFile, location, error);
+        return None;
+    }
+    provenance.record(Source::LsbReleaseFile, location, SourceStatus::Parsed);
+    Some(info)
+}
+
+/// Reads the `/etc/upstream-release/lsb-release` file under the given root directory, which some
+/// derivatives such as Linux Mint and elementary OS ship to describe the release they are based on.
+pub fn upstream(root: &Path) -> Option<Info> {
+    let path = rootfs::resolve(root, Path::new("/etc/upstream-release/lsb-release"));
+    match fs::read_to_string(&path) {
+        Ok(content) => Some(parse_file(&content))
+            .filter(|release| release.distribution.is_some())
+            .map(info),
+        Err(e) => {
+            debug!("Unable to read {:?} file: {:?}", path, e);
+            None
+        }
+    }
+}
 
+fn info(release: LsbRelease) -> Info {
     let version = match release.version.as_deref() {
         Some("rolling") => Version::Rolling(None),
         Some(v) => Version::Custom(v.to_owned()),
         None => Version::Unknown,
     };
 
-    let os_type = match release.distribution.as_ref().map(String::as_ref) {
-        Some("Amazon") | Some("AmazonAMI") => Type::Amazon,
-        Some("Arch") => Type::Arch,
-        Some("CentOS") => Type::CentOS,
-        Some("Debian") => Type::Debian,
-        Some("EndeavourOS") => Type::EndeavourOS,
-        Some("Fedora") | Some("Fedora Linux") => Type::Fedora,
-        Some("Linuxmint") => Type::Mint,
-        Some("ManjaroLinux") => Type::Manjaro,
-        Some("Mariner") => Type::Mariner,
-        Some("NixOS") => Type::NixOS,
-        Some("openSUSE") => Type::openSUSE,
-        Some("OracleServer") => Type::OracleLinux,
-        Some("Pop") => Type::Pop,
-        Some("Raspbian") => Type::Raspbian,
-        Some("RedHatEnterprise") | Some("RedHatEnterpriseServer") => Type::RedHatEnterprise,
-        Some("Solus") => Type::Solus,
-        Some("SUSE") => Type::SUSE,
-        Some("Ubuntu") => Type::Ubuntu,
-        _ => Type::Linux,
+    let distribution = release.distribution.as_deref();
+    let os_type = match distribution.and_then(registry::from_distributor_id) {
+        Some(os_type) => os_type,
+        None => match distribution {
+            Some("Amazon") | Some("AmazonAMI") => Type::Amazon,
+            Some("Arch") => Type::Arch,
+            Some("CentOS") => Type::CentOS,
+            Some("Debian") => Type::Debian,
+            Some("EndeavourOS") => Type::EndeavourOS,
+            Some("Fedora") | Some("Fedora Linux") => Type::Fedora,
+            Some("Linuxmint") | Some("LinuxMint") => Type::Mint,
+            Some("ManjaroLinux") => Type::Manjaro,
+            Some("Mariner") => Type::Mariner,
+            Some("NixOS") => Type::NixOS,
+            Some("openSUSE") => Type::openSUSE,
+            Some("OracleServer") => Type::OracleLinux,
+            Some("Pop") => Type::Pop,
+            Some("Raspbian") => Type::Raspbian,
+            Some("RedHatEnterprise")
+            | Some("RedHatEnterpriseServer")
+            | Some("RedHatEnterpriseWorkstation") => Type::RedHatEnterprise,
+            Some("Solus") => Type::Solus,
+            Some("SUSE") => Type::SUSE,
+            Some("Ubuntu") => Type::Ubuntu,
+            _ => Type::Linux,
+        },
+    };
+
+    // RHEL 7 and earlier report the variant as a part of the distributor ID.
+    let edition = match distribution {
+        Some("RedHatEnterpriseServer") => Some("Server".to_owned()),
+        Some("RedHatEnterpriseWorkstation") => Some("Workstation".to_owned()),
+        _ => None,
     };
 
-    Some(Info {
+    Info {
         os_type,
         version,
+        edition,
         codename: release.codename,
         ..Default::default()
-    })
+    }
 }
 
 struct LsbRelease {
@@ -51,19 +119,70 @@ struct LsbRelease {
     pub codename: Option<String>,
 }
 
-fn retrieve() -> Option<LsbRelease> {
-    match Command::new("lsb_release").arg("-a").output() {
-        Ok(output) => {
+fn retrieve(timeout: Option<Duration>, provenance: &mut Provenance) -> Option<LsbRelease> {
+    const COMMAND: &str = "lsb_release -a";
+
+    match command::output(Command::new("lsb_release").arg("-a"), timeout) {
+        Ok(output) if output.status.success() => {
             trace!("lsb_release command returned {:?}", output);
-            Some(parse(&String::from_utf8_lossy(&output.stdout)))
+            let release = parse(&String::from_utf8_lossy(&output.stdout));
+            record(provenance, Source::LsbRelease, COMMAND, &release);
+            Some(release)
+        }
+        Ok(output) => {
+            debug!("lsb_release command returned {:?}", output);
+            let error = DetectionError::CommandFailed {
+                command: COMMAND.to_owned(),
+                message: output.status.to_string(),
+            };
+            provenance.record_error(Source::LsbRelease, COMMAND, error);
+            None
         }
         Err(e) => {
             debug!("lsb_release command failed with {:?}", e);
+            let error = DetectionError::command(COMMAND, &e);
+            provenance.record_error(Source::LsbRelease, COMMAND, error);
             None
         }
     }
 }
 
+fn retrieve_from_file(root: &Path, provenance: &mut Provenance) -> Option<LsbRelease> {
+    let path = rootfs::resolve(root, Path::new("/etc/lsb-release"));
+    let location = path.display().to_string();
+    match fs::read_to_string(&path) {
+        Ok(content) => {
+            let release = parse_file(&content);
+            record(provenance, Source::LsbReleaseFile, location, &release);
+            Some(release)
+        }
+        Err(e) => {
+            debug!("Unable to read {:?} file: {:?}", path, e);
+            let error = DetectionError::file(path, &e);
+            provenance.record_error(Source::LsbReleaseFile, location, error);
+            None
+        }
+    }
+}
+
+fn record<S: Into<String>>(
+    provenance: &mut Provenance,
+    source: Source,
+    location: S,
+    release: &LsbRelease,
+) {
+    let location = location.into();
+    if release.distribution.is_some() {
+        provenance.record(source, location, SourceStatus::Parsed);
+    } else {
+        let error = DetectionError::UnparseableContent {
+            location: location.clone(),
+            message: "distributor ID is missing".to_owned(),
+        };
+        provenance.record_error(source, location, error);
+    }
+}
+
 fn parse(output: &str) -> LsbRelease {
     trace!("Trying to parse {:?}", output);
 
@@ -93,6 +212,48 @@ fn parse(output: &str) -> LsbRelease {
     }
 }
 
+/// Mimics the `lsb_release` script used by Debian and its derivatives, which capitalizes `ID`
+/// unless `NAME` only differs from it in case.
+fn from_os_release(os_release: &OsRelease) -> LsbRelease {
+    let distribution = os_release.id().map(|id| match os_release.name() {
+        Some(name) if name.eq_ignore_ascii_case(id) => name.to_owned(),
+        _ => {
+            let mut chars = id.chars();
+            chars
+                .next()
+                .map(|first| first.to_uppercase().chain(chars).collect())
+                .unwrap_or_default()
+        }
+    });
+    let find = |value: Option<&str>| value.filter(|v| !v.is_empty()).map(str::to_owned);
+
+    LsbRelease {
+        // The command output is parsed up to the first space.
+        distribution: distribution
+            .as_deref()
+            .and_then(|d| d.split_whitespace().next())
+            .map(str::to_owned),
+        version: find(os_release.version_id()),
+        codename: find(os_release.version_codename()),
+    }
+}
+
+fn parse_file(file: &str) -> LsbRelease {
+    trace!("Trying to parse {:?}", file);
+
+    let find = |key| {
+        Matcher::KeyValue { key }
+            .find(file)
+            .filter(|v| !v.is_empty() && v != "n/a")
+    };
+
+    LsbRelease {
+        distribution: find("DISTRIB_ID"),
+        version: find("DISTRIB_RELEASE"),
+        codename: find("DISTRIB_CODENAME"),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -270,7 +431,6 @@ mod tests {
         assert_eq!(parse_results.codename, Some("Mariner".to_string()));
     }
 
-
     #[test]
     fn endeavouros() {
         let parse_results = parse(endeavouros_file());
@@ -287,6 +447,131 @@ mod tests {
         assert_eq!(parse_results.codename, None);
     }
 
+    #[test]
+    fn ubuntu_lsb_release_file() {
+        let parse_results = parse_file(ubuntu_lsb_release());
+        assert_eq!(parse_results.distribution, Some("Ubuntu".to_string()));
+        assert_eq!(parse_results.version, Some("16.04".to_string()));
+        assert_eq!(parse_results.codename, Some("xenial".to_string()));
+    }
+
+    #[test]
+    fn mint_lsb_release_file() {
+        let parse_results = parse_file(mint_lsb_release());
+        assert_eq!(parse_results.distribution, Some("LinuxMint".to_string()));
+        assert_eq!(parse_results.version, Some("20".to_string()));
+        assert_eq!(parse_results.codename, Some("ulyana".to_string()));
+        assert_eq!(info(parse_results).os_type(), Type::Mint);
+    }
+
+    #[test]
+    fn registered_distributor_id() {
+        crate::Distribution::new(Type::RedHatEnterprise)
+            .distributor_id("ExampleEnterprise")
+            .register();
+        let release = LsbRelease {
+            distribution: Some("ExampleEnterprise".to_owned()),
+            version: Some("9.2".to_owned()),
+            codename: None,
+        };
+        assert_eq!(info(release).os_type(), Type::RedHatEnterprise);
+    }
+
+    #[test]
+    fn root_directory() {
+        let mut provenance = Provenance::default();
+        let info =
+            get_from_root(Path::new("src/linux/tests/rootfs-ubuntu"), &mut provenance).unwrap();
+        assert_eq!(info.os_type(), Type::Ubuntu);
+        assert_eq!(info.version, Version::Custom("18.10".to_owned()));
+        assert_eq!(info.codename, Some("cosmic".to_owned()));
+        assert_eq!(provenance.records()[0].status(), SourceStatus::Parsed);
+    }
+
+    #[test]
+    fn missing_file() {
+        let mut provenance = Provenance::default();
+        assert!(
+            get_from_root(Path::new("src/linux/tests/rootfs-alpine"), &mut provenance).is_none()
+        );
+        assert_eq!(provenance.records()[0].status(), SourceStatus::Missing);
+        assert_eq!(
+            provenance.records()[0].location(),
+            "src/linux/tests/rootfs-alpine/etc/lsb-release"
+        );
+    }
+
+    #[test]
+    fn os_release_emulation() {
+        let data = [
+            (
+                "debian",
+                Some("Debian"),
+                Some("11"),
+                Some("bullseye"),
+                Type::Debian,
+            ),
+            ("fedora-35", Some("Fedora"), Some("35"), None, Type::Fedora),
+            (
+                "mint",
+                Some("Linuxmint"),
+                Some("20"),
+                Some("ulyana"),
+                Type::Mint,
+            ),
+            (
+                "nixos",
+                Some("NixOS"),
+                Some("21.05pre275822.916ee862e87"),
+                Some("okapi"),
+                Type::NixOS,
+            ),
+            ("rhel", Some("Rhel"), Some("8.2"), None, Type::Linux),
+        ];
+
+        for (name, distribution, version, codename, os_type) in &data {
+            let path = format!("src/linux/tests/os-release-{}", name);
+            let content = fs::read_to_string(path).unwrap();
+            let release = from_os_release(&OsRelease::parse(&content));
+            assert_eq!(release.distribution.as_deref(), *distribution, "{}", name);
+            assert_eq!(release.version.as_deref(), *version, "{}", name);
+            assert_eq!(release.codename.as_deref(), *codename, "{}", name);
+            assert_eq!(info(release).os_type(), *os_type, "{}", name);
+        }
+    }
+
+    #[test]
+    fn files() {
+        let mut provenance = Provenance::default();
+        let info =
+            get_from_files(Path::new("src/linux/tests/rootfs-fedora"), &mut provenance).unwrap();
+        assert_eq!(info.os_type(), Type::Fedora);
+        assert_eq!(info.version, Version::Custom("35".to_owned()));
+        let statuses: Vec<_> = provenance.records().iter().map(|r| r.status()).collect();
+        assert_eq!(statuses, [SourceStatus::Missing, SourceStatus::Parsed]);
+
+        // Unlike the release files, `lsb_release` doesn't recognize Alpine Linux.
+        let mut provenance = Provenance::default();
+        assert!(
+            get_from_files(Path::new("src/linux/tests/rootfs-alpine"), &mut provenance).is_none()
+        );
+        assert_eq!(provenance.records()[1].status(), SourceStatus::Unrecognized);
+    }
+
+    fn ubuntu_lsb_release() -> &'static str {
+        "DISTRIB_ID=Ubuntu\n\
+         DISTRIB_RELEASE=16.04\n\
+         DISTRIB_CODENAME=xenial\n\
+         DISTRIB_DESCRIPTION=\"Ubuntu 16.04.7 LTS\"\n"
+    }
+
+    fn mint_lsb_release() -> &'static str {
+        "DISTRIB_ID=LinuxMint\n\
+         DISTRIB_RELEASE=20\n\
+         DISTRIB_CODENAME=ulyana\n\
+         DISTRIB_DESCRIPTION=\"Linux Mint 20 Ulyana\"\n"
+    }
+
     fn file() -> &'static str {
         "\nDistributor ID:	Debian\n\
          Description:	Debian GNU/Linux 7.8 (wheezy)\n\
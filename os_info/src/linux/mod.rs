@@ -1,29 +1,110 @@
 mod file_release;
 mod lsb_release;
+mod rootfs;
+
+use std::path::Path;
 
 use log::trace;
 
-use crate::{bitness, Info, Type};
+use crate::{
+    architecture, bitness, uname::uname, Architecture, Bitness, Detector, Info, Provenance, Source,
+    SourceStatus, Type,
+};
+
+/// Executables whose ELF header is used to determine the bitness and architecture of a system
+/// under a root directory.
+const ELF_PROBES: [&str; 3] = ["/bin/sh", "/usr/bin/env", "/bin/ls"];
+
+pub fn current_platform(detector: &Detector) -> Info {
+    trace!("linux::current_platform({:?}) is called", detector);
+
+    // The `lsb_release` command (and its emulation) and the running kernel can only describe the
+    // current system.
+    let host = detector.root.is_none();
+    let root = detector.root.as_deref().unwrap_or_else(|| Path::new("/"));
 
-pub fn current_platform() -> Info {
-    trace!("linux::current_platform is called");
+    let mut provenance = Provenance::default();
+    let mut info = None;
+    for &source in &detector.sources {
+        info = match source {
+            Source::LsbRelease if host => lsb_release::get(detector.timeout, &mut provenance),
+            Source::LsbReleaseFile if host => lsb_release::get_from_files(root, &mut provenance),
+            Source::LsbRelease | Source::LsbReleaseFile => {
+                lsb_release::get_from_root(root, &mut provenance)
+            }
+            Source::ReleaseFile | Source::OsRelease => {
+                file_release::get_from_root(root, &[source], &mut provenance)
+            }
+            _ => None,
+        };
+        if info.is_some() {
+            break;
+        }
+    }
 
-    let mut info = lsb_release::get()
-        .or_else(file_release::get)
-        .unwrap_or_else(|| Info::with_type(Type::Linux));
-    info.bitness = bitness::get();
+    let mut info = info.unwrap_or_else(|| fallback(&mut provenance));
+    provenance.select(&info);
+    info.provenance = provenance;
+
+    if host {
+        info.bitness = if detector.uses(Source::Getconf) {
+            bitness::get(detector.timeout)
+        } else {
+            match probe_elf(root, bitness::from_elf, Bitness::Unknown) {
+                Bitness::Unknown => bitness::from_auxv(),
+                bitness => bitness,
+            }
+        };
+        info.architecture = architecture::get();
+        info.kernel = uname().ok();
+    } else {
+        info.bitness = probe_elf(root, bitness::from_elf, Bitness::Unknown);
+        info.architecture = probe_elf(root, architecture::from_elf, Architecture::Unknown);
+    }
+    if detector.uses(Source::OsRelease) {
+        info.os_release = file_release::os_release(root);
+    }
+    if detector.uses(Source::LsbRelease) || detector.uses(Source::LsbReleaseFile) {
+        info.upstream = lsb_release::upstream(root).map(Box::new);
+    }
+    // The `lsb_release` command and release files other than `os-release` rarely report the
+    // variant of the distribution.
+    if info.edition.is_none() {
+        info.edition = info.os_release.as_ref().and_then(file_release::variant);
+    }
+    if info.edition.is_none() && info.os_type == Type::Ubuntu {
+        info.edition = file_release::ubuntu_edition(root);
+    }
 
     trace!("Returning {:?}", info);
     info
 }
 
+fn fallback(provenance: &mut Provenance) -> Info {
+    provenance.record(Source::Fallback, "generic Linux", SourceStatus::Parsed);
+    Info::with_type(Type::Linux)
+}
+
+/// Returns the first known value extracted from the ELF probes under the root directory.
+fn probe_elf<T: PartialEq>(root: &Path, extract: fn(&Path) -> T, unknown: T) -> T {
+    ELF_PROBES
+        .iter()
+        .map(|probe| extract(&rootfs::resolve(root, Path::new(probe))))
+        .find(|value| *value != unknown)
+        .unwrap_or(unknown)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn detect_root(root: &str) -> Info {
+        current_platform(&Detector::new().root(root))
+    }
+
     #[test]
     fn os_type() {
-        let version = current_platform();
+        let version = current_platform(&Detector::default());
         match version.os_type() {
             Type::Alpine
             | Type::Amazon
@@ -51,4 +132,140 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn kernel() {
+        let info = current_platform(&Detector::default());
+        let kernel = info.kernel().expect("kernel info is missing");
+        assert_eq!(kernel.sysname(), "Linux");
+        assert_ne!(kernel.release_version(), crate::Version::Unknown);
+
+        // The running kernel says nothing about a system under another root.
+        let info = detect_root("src/linux/tests/rootfs-alpine");
+        assert_eq!(info.kernel(), None);
+    }
+
+    #[test]
+    fn from_root() {
+        let info = detect_root("src/linux/tests/rootfs-alpine");
+        assert_eq!(info.os_type(), Type::Alpine);
+        assert_eq!(info.bitness(), Bitness::X64);
+        assert_eq!(info.architecture(), Architecture::X86_64);
+        assert_eq!(info.os_release().and_then(|r| r.id()), Some("alpine"));
+
+        let info = detect_root("src/linux/tests/rootfs-ubuntu");
+        assert_eq!(info.os_type(), Type::Ubuntu);
+        assert_eq!(info.codename(), Some("cosmic"));
+        assert_eq!(info.edition(), None);
+        assert_eq!(info.bitness(), Bitness::Unknown);
+        assert_eq!(info.architecture(), Architecture::Unknown);
+
+        let info = detect_root("src/linux/tests/rootfs-ubuntu-server");
+        assert_eq!(info.os_type(), Type::Ubuntu);
+        assert_eq!(info.edition(), Some("Server"));
+    }
+
+    #[test]
+    fn upstream() {
+        let info = detect_root("src/linux/tests/rootfs-mint");
+        assert_eq!(info.os_type(), Type::Mint);
+        let upstream = info.upstream().unwrap();
+        assert_eq!(upstream.os_type(), Type::Ubuntu);
+        assert_eq!(
+            upstream.version(),
+            &crate::Version::Custom("20.04".to_owned())
+        );
+        assert_eq!(upstream.codename(), Some("focal"));
+
+        // Without the upstream release file, the base is determined from `UBUNTU_CODENAME`.
+        let info = current_platform(
+            &Detector::new()
+                .root("src/linux/tests/rootfs-mint")
+                .sources(vec![Source::OsRelease]),
+        );
+        assert_eq!(info.os_type(), Type::Mint);
+        let upstream = info.upstream().unwrap();
+        assert_eq!(upstream.version(), &crate::Version::Semantic(20, 4, 0));
+
+        let info = detect_root("src/linux/tests/rootfs-ubuntu");
+        assert_eq!(info.upstream(), None);
+    }
+
+    #[test]
+    fn from_empty_root() {
+        let info = detect_root("src/linux/tests/rootfs-missing");
+        assert_eq!(info.os_type(), Type::Linux);
+        assert_eq!(info.version(), &crate::Version::Unknown);
+        assert_eq!(info.bitness(), Bitness::Unknown);
+        assert_eq!(info.os_release(), None);
+        assert_eq!(info.provenance().confidence(), crate::Confidence::Unknown);
+        assert_eq!(
+            info.provenance().selected().map(|r| r.source()),
+            Some(Source::Fallback)
+        );
+    }
+
+    #[test]
+    fn provenance() {
+        let info = detect_root("src/linux/tests/rootfs-ubuntu");
+        let provenance = info.provenance();
+        assert_eq!(provenance.records().len(), 1);
+        assert_eq!(
+            provenance.selected().map(|r| r.source()),
+            Some(Source::LsbReleaseFile)
+        );
+        assert_eq!(provenance.confidence(), crate::Confidence::High);
+
+        let info = detect_root("src/linux/tests/rootfs-fedora");
+        let selected = info.provenance().selected().unwrap();
+        assert_eq!(selected.source(), Source::OsRelease);
+        assert_eq!(
+            selected.location(),
+            "src/linux/tests/rootfs-fedora/usr/lib/os-release"
+        );
+        assert_eq!(info.provenance().confidence(), crate::Confidence::High);
+
+        let info = current_platform(&Detector::default());
+        assert!(info.provenance().selected().is_some());
+    }
+
+    #[test]
+    fn source_order() {
+        let root = "src/linux/tests/rootfs-ubuntu";
+        let info = current_platform(
+            &Detector::new()
+                .root(root)
+                .sources(vec![Source::OsRelease, Source::LsbRelease]),
+        );
+        assert_eq!(info.os_type(), Type::Ubuntu);
+        assert_eq!(info.version(), &crate::Version::Semantic(18, 10, 0));
+        assert_eq!(info.codename(), Some("cosmic"));
+        assert_eq!(
+            info.provenance().selected().map(|r| r.source()),
+            Some(Source::OsRelease)
+        );
+
+        let info = current_platform(&Detector::new().root(root).sources(vec![]));
+        assert_eq!(info.os_type(), Type::Linux);
+        assert_eq!(info.os_release(), None);
+    }
+
+    #[cfg(not(feature = "no-subprocess"))]
+    #[test]
+    fn without_getconf() {
+        let info = current_platform(&Detector::new().without(Source::Getconf));
+        assert_eq!(info.bitness(), bitness::get(None));
+    }
+
+    #[test]
+    fn subprocess_free() {
+        let info = current_platform(&Detector::default());
+        let subprocess_free = current_platform(&Detector::default().subprocess_free());
+        assert_eq!(info.os_type(), subprocess_free.os_type());
+        assert_eq!(info.version(), subprocess_free.version());
+        assert_eq!(info.codename(), subprocess_free.codename());
+        assert_eq!(info.bitness(), subprocess_free.bitness());
+        assert_eq!(info.architecture(), subprocess_free.architecture());
+        assert_eq!(info.kernel(), subprocess_free.kernel());
+    }
 }
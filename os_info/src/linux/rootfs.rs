@@ -0,0 +1,85 @@
+use std::{
+    ffi::OsString,
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+/// Maximum number of symbolic links followed while resolving a single path.
+const MAX_SYMLINK_HOPS: usize = 40;
+
+/// Resolves `path` as if the process was chrooted into `root`.
+///
+/// Symbolic links are followed manually, so that absolute link targets (for example
+/// `/bin/sh -> /bin/busybox`) point inside the root directory instead of the host filesystem.
+pub fn resolve(root: &Path, path: &Path) -> PathBuf {
+    let mut resolved = PathBuf::new();
+    let mut pending: Vec<OsString> = components(path);
+    let mut hops = 0;
+
+    while let Some(component) = pending.pop() {
+        if component == ".." {
+            resolved.pop();
+            continue;
+        }
+
+        let candidate = resolved.join(&component);
+        match fs::read_link(root.join(&candidate)) {
+            Ok(target) if hops < MAX_SYMLINK_HOPS => {
+                hops += 1;
+                if target.is_absolute() {
+                    resolved = PathBuf::new();
+                }
+                pending.extend(components(&target));
+            }
+            _ => resolved = candidate,
+        }
+    }
+
+    root.join(resolved)
+}
+
+/// Returns normal and parent components of the path in reverse order, so they can be consumed by
+/// popping from the end.
+fn components(path: &Path) -> Vec<OsString> {
+    path.components()
+        .rev()
+        .filter_map(|c| match c {
+            Component::Normal(name) => Some(name.to_owned()),
+            Component::ParentDir => Some(OsString::from("..")),
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn plain_path() {
+        let root = Path::new("src/linux/tests/rootfs-alpine");
+        assert_eq!(
+            resolve(root, Path::new("/etc/alpine-release")),
+            root.join("etc/alpine-release")
+        );
+    }
+
+    #[test]
+    fn absolute_symlink_stays_inside_root() {
+        let root = Path::new("src/linux/tests/rootfs-alpine");
+        assert_eq!(
+            resolve(root, Path::new("/etc/os-release")),
+            root.join("usr/lib/os-release")
+        );
+    }
+
+    #[test]
+    fn parent_dir_does_not_escape_root() {
+        let root = Path::new("src/linux/tests/rootfs-alpine");
+        assert_eq!(
+            resolve(root, Path::new("/../../etc/alpine-release")),
+            root.join("etc/alpine-release")
+        );
+    }
+}
@@ -1,38 +1,105 @@
-use std::process::Command;
+// spell-checker:ignore osproductversion, sysctl
 
-use log::{trace, warn};
+use std::{process::Command, time::Duration};
 
-use crate::{bitness, matcher::Matcher, Info, Type, Version};
+use log::{trace, warn};
 
-pub fn current_platform() -> Info {
-    trace!("macos::current_platform is called");
+use crate::{
+    architecture, bitness, command, matcher::Matcher, sysctl::sysctl, uname::uname, DetectionError,
+    Detector, Info, Provenance, Source, SourceStatus, Type, Version,
+};
+
+/// Name of the `sysctl` value holding the product version, for example, `14.2.1`.
+const PRODUCT_VERSION: &str = "kern.osproductversion";
+
+pub fn current_platform(detector: &Detector) -> Info {
+    trace!("macos::current_platform({:?}) is called", detector);
+
+    let mut provenance = Provenance::default();
+    let version = detector
+        .sources
+        .iter()
+        .find_map(|source| match source {
+            Source::Command => product_version(detector.timeout, &mut provenance),
+            Source::Sysctl => sysctl_product_version(&mut provenance),
+            _ => None,
+        })
+        .map(Version::from_string)
+        .unwrap_or(Version::Unknown);
+    if version == Version::Unknown {
+        provenance.record_target();
+    }
 
-    let info = Info {
+    let architecture = architecture::get();
+    let mut info = Info {
         os_type: Type::Macos,
-        version: version(),
-        bitness: bitness::get(),
+        version,
+        bitness: bitness::detect(detector, architecture),
+        architecture,
+        kernel: uname().ok(),
         ..Default::default()
     };
+    provenance.select(&info);
+    info.provenance = provenance;
     trace!("Returning {:?}", info);
     info
 }
 
-fn version() -> Version {
-    match product_version() {
-        None => Version::Unknown,
-        Some(val) => Version::from_string(val),
-    }
-}
-
-fn product_version() -> Option<String> {
-    match Command::new("sw_vers").output() {
-        Ok(val) => {
+fn product_version(timeout: Option<Duration>, provenance: &mut Provenance) -> Option<String> {
+    match command::output(&mut Command::new("sw_vers"), timeout) {
+        Ok(val) if val.status.success() => {
             let output = String::from_utf8_lossy(&val.stdout);
             trace!("sw_vers command returned {:?}", output);
-            parse(&output)
+            let version = parse(&output);
+            match version {
+                Some(_) => provenance.record(Source::Command, "sw_vers", SourceStatus::Parsed),
+                None => {
+                    let error = DetectionError::UnparseableContent {
+                        location: "sw_vers".to_owned(),
+                        message: "product version is missing".to_owned(),
+                    };
+                    provenance.record_error(Source::Command, "sw_vers", error);
+                }
+            }
+            version
+        }
+        Ok(val) => {
+            warn!("sw_vers command returned {:?}", val);
+            let error = DetectionError::CommandFailed {
+                command: "sw_vers".to_owned(),
+                message: val.status.to_string(),
+            };
+            provenance.record_error(Source::Command, "sw_vers", error);
+            None
         }
         Err(e) => {
             warn!("sw_vers command failed with {:?}", e);
+            let error = DetectionError::command("sw_vers", &e);
+            provenance.record_error(Source::Command, "sw_vers", error);
+            None
+        }
+    }
+}
+
+/// Reads the product version using the `sysctl` system call, which is available since macOS 10.13.4.
+fn sysctl_product_version(provenance: &mut Provenance) -> Option<String> {
+    match sysctl(PRODUCT_VERSION) {
+        Ok(version) if !version.is_empty() => {
+            trace!("{} is {:?}", PRODUCT_VERSION, version);
+            provenance.record(Source::Sysctl, PRODUCT_VERSION, SourceStatus::Parsed);
+            Some(version)
+        }
+        Ok(_) => {
+            let error = DetectionError::UnparseableContent {
+                location: PRODUCT_VERSION.to_owned(),
+                message: "product version is empty".to_owned(),
+            };
+            provenance.record_error(Source::Sysctl, PRODUCT_VERSION, error);
+            None
+        }
+        Err(e) => {
+            warn!("Unable to read {}: {}", PRODUCT_VERSION, e);
+            provenance.record_error(Source::Sysctl, PRODUCT_VERSION, e);
             None
         }
     }
@@ -52,22 +119,30 @@ mod tests {
 
     #[test]
     fn os_type() {
-        let version = current_platform();
+        let version = current_platform(&Detector::default());
         assert_eq!(Type::Macos, version.os_type());
     }
 
     #[test]
     fn os_version() {
-        let version = version();
+        let version = current_platform(&Detector::default()).version().clone();
         assert_ne!(Version::Unknown, version);
     }
 
     #[test]
     fn string_product_version() {
-        let version = product_version();
+        let version = product_version(None, &mut Provenance::default());
         assert!(version.is_some());
     }
 
+    #[test]
+    fn subprocess_free() {
+        let info = current_platform(&Detector::default());
+        let subprocess_free = current_platform(&Detector::default().subprocess_free());
+        assert_eq!(info.version(), subprocess_free.version());
+        assert_eq!(info.bitness(), subprocess_free.bitness());
+    }
+
     #[test]
     fn parse_version() {
         let parse_output = parse(sw_vers_output());
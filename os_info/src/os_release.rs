@@ -0,0 +1,417 @@
+// spell-checker:ignore cpe, sysext, ansi
+
+use std::{collections::BTreeMap, str::Chars};
+
+use log::trace;
+
+/// Parsed contents of the `os-release` file.
+///
+/// The file format is described by the freedesktop.org specification:
+/// <https://www.freedesktop.org/software/systemd/man/os-release.html>. Every assignment is kept,
+/// including vendor-specific extensions such as `UBUNTU_CODENAME`, which can be retrieved with the
+/// `get` method.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::OsRelease;
+///
+/// let os_release = OsRelease::parse(
+///     "NAME=\"Ubuntu\"\nID=ubuntu\nID_LIKE=debian\nVERSION_ID=\"22.04\"\nUBUNTU_CODENAME=jammy\n",
+/// );
+/// assert_eq!(Some("Ubuntu"), os_release.name());
+/// assert_eq!(Some("ubuntu"), os_release.id());
+/// assert_eq!(vec!["debian"], os_release.id_like());
+/// assert_eq!(Some("22.04"), os_release.version_id());
+/// assert_eq!(Some("jammy"), os_release.get("UBUNTU_CODENAME"));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+pub struct OsRelease {
+    fields: BTreeMap<String, String>,
+}
+
+impl OsRelease {
+    /// Parses the content of an `os-release` file.
+    ///
+    /// Values can be quoted with single or double quotes and can contain backslash escapes, as in
+    /// shell. Comments, empty lines and lines that aren't valid assignments are ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::OsRelease;
+    ///
+    /// let os_release = OsRelease::parse("# Comment\nPRETTY_NAME='Fedora Linux 35 (Workstation Edition)'");
+    /// assert_eq!(Some("Fedora Linux 35 (Workstation Edition)"), os_release.pretty_name());
+    /// ```
+    pub fn parse(content: &str) -> Self {
+        let fields = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .filter_map(|line| {
+                let assignment = parse_assignment(line);
+                if assignment.is_none() {
+                    trace!("Ignoring invalid os-release line {:?}", line);
+                }
+                assignment
+            })
+            .collect();
+
+        Self { fields }
+    }
+
+    /// Returns the value of the given variable, including vendor-specific ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::OsRelease;
+    ///
+    /// let os_release = OsRelease::parse("DEBIAN_CODENAME=bookworm");
+    /// assert_eq!(Some("bookworm"), os_release.get("DEBIAN_CODENAME"));
+    /// assert_eq!(None, os_release.get("NAME"));
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).map(String::as_str)
+    }
+
+    /// Returns an iterator over all variables in the alphabetical order of their names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::OsRelease;
+    ///
+    /// let os_release = OsRelease::parse("NAME=Fedora\nID=fedora");
+    /// let keys: Vec<_> = os_release.iter().map(|(key, _)| key).collect();
+    /// assert_eq!(vec!["ID", "NAME"], keys);
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.fields.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Returns `true` if the file didn't contain any variables.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::OsRelease;
+    ///
+    /// assert!(OsRelease::parse("# Only a comment").is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns the operating system name without a version component (`NAME`). The specification
+    /// suggests `Linux` as the default value.
+    pub fn name(&self) -> Option<&str> {
+        self.get("NAME")
+    }
+
+    /// Returns the lower-case operating system identifier (`ID`). The specification suggests
+    /// `linux` as the default value.
+    pub fn id(&self) -> Option<&str> {
+        self.get("ID")
+    }
+
+    /// Returns identifiers of operating systems that the local one is derived from (`ID_LIKE`),
+    /// ordered from the closest to the most distant relative.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::OsRelease;
+    ///
+    /// let os_release = OsRelease::parse("ID_LIKE=\"rhel centos fedora\"");
+    /// assert_eq!(vec!["rhel", "centos", "fedora"], os_release.id_like());
+    /// ```
+    pub fn id_like(&self) -> Vec<&str> {
+        self.get("ID_LIKE")
+            .map(|v| v.split_whitespace().collect())
+            .unwrap_or_default()
+    }
+
+    /// Returns the operating system name in a format suitable for presentation to the user
+    /// (`PRETTY_NAME`).
+    pub fn pretty_name(&self) -> Option<&str> {
+        self.get("PRETTY_NAME")
+    }
+
+    /// Returns the CPE name of the operating system (`CPE_NAME`).
+    pub fn cpe_name(&self) -> Option<&str> {
+        self.get("CPE_NAME")
+    }
+
+    /// Returns the operating system variant (`VARIANT`), for example `Workstation Edition`.
+    pub fn variant(&self) -> Option<&str> {
+        self.get("VARIANT")
+    }
+
+    /// Returns the lower-case operating system variant identifier (`VARIANT_ID`).
+    pub fn variant_id(&self) -> Option<&str> {
+        self.get("VARIANT_ID")
+    }
+
+    /// Returns the operating system version, possibly including a release code name (`VERSION`).
+    pub fn version(&self) -> Option<&str> {
+        self.get("VERSION")
+    }
+
+    /// Returns the lower-case operating system version identifier (`VERSION_ID`).
+    pub fn version_id(&self) -> Option<&str> {
+        self.get("VERSION_ID")
+    }
+
+    /// Returns the lower-case release code name (`VERSION_CODENAME`).
+    pub fn version_codename(&self) -> Option<&str> {
+        self.get("VERSION_CODENAME")
+    }
+
+    /// Returns the build identifier of the system image (`BUILD_ID`).
+    pub fn build_id(&self) -> Option<&str> {
+        self.get("BUILD_ID")
+    }
+
+    /// Returns the identifier of the system image (`IMAGE_ID`).
+    pub fn image_id(&self) -> Option<&str> {
+        self.get("IMAGE_ID")
+    }
+
+    /// Returns the version of the system image (`IMAGE_VERSION`).
+    pub fn image_version(&self) -> Option<&str> {
+        self.get("IMAGE_VERSION")
+    }
+
+    /// Returns the homepage of the operating system (`HOME_URL`).
+    pub fn home_url(&self) -> Option<&str> {
+        self.get("HOME_URL")
+    }
+
+    /// Returns the documentation page of the operating system (`DOCUMENTATION_URL`).
+    pub fn documentation_url(&self) -> Option<&str> {
+        self.get("DOCUMENTATION_URL")
+    }
+
+    /// Returns the support page of the operating system (`SUPPORT_URL`).
+    pub fn support_url(&self) -> Option<&str> {
+        self.get("SUPPORT_URL")
+    }
+
+    /// Returns the bug tracker of the operating system (`BUG_REPORT_URL`).
+    pub fn bug_report_url(&self) -> Option<&str> {
+        self.get("BUG_REPORT_URL")
+    }
+
+    /// Returns the privacy policy of the operating system (`PRIVACY_POLICY_URL`).
+    pub fn privacy_policy_url(&self) -> Option<&str> {
+        self.get("PRIVACY_POLICY_URL")
+    }
+
+    /// Returns the end of support date of the operating system (`SUPPORT_END`) in the
+    /// `YYYY-MM-DD` format.
+    pub fn support_end(&self) -> Option<&str> {
+        self.get("SUPPORT_END")
+    }
+
+    /// Returns the name of the logo icon (`LOGO`).
+    pub fn logo(&self) -> Option<&str> {
+        self.get("LOGO")
+    }
+
+    /// Returns the suggested presentation color (`ANSI_COLOR`).
+    pub fn ansi_color(&self) -> Option<&str> {
+        self.get("ANSI_COLOR")
+    }
+
+    /// Returns the name of the operating system vendor (`VENDOR_NAME`).
+    pub fn vendor_name(&self) -> Option<&str> {
+        self.get("VENDOR_NAME")
+    }
+
+    /// Returns the homepage of the operating system vendor (`VENDOR_URL`).
+    pub fn vendor_url(&self) -> Option<&str> {
+        self.get("VENDOR_URL")
+    }
+
+    /// Returns the default hostname (`DEFAULT_HOSTNAME`).
+    pub fn default_hostname(&self) -> Option<&str> {
+        self.get("DEFAULT_HOSTNAME")
+    }
+
+    /// Returns the system extension level (`SYSEXT_LEVEL`).
+    pub fn sysext_level(&self) -> Option<&str> {
+        self.get("SYSEXT_LEVEL")
+    }
+}
+
+fn parse_assignment(line: &str) -> Option<(String, String)> {
+    let (key, value) = line.split_once('=')?;
+    if key.is_empty()
+        || key.starts_with(|c: char| c.is_ascii_digit())
+        || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+
+    Some((key.to_owned(), unquote(value)?))
+}
+
+/// Expands a shell-style value: single quotes are taken literally, double quotes allow escaping
+/// `"`, `\`, `$` and `` ` `` with a backslash, and outside of quotes a backslash escapes any
+/// character. Returns `None` if a quote isn't terminated.
+fn unquote(value: &str) -> Option<String> {
+    let mut result = String::with_capacity(value.len());
+    let mut chars = value.trim_end().chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => loop {
+                match chars.next()? {
+                    '\'' => break,
+                    c => result.push(c),
+                }
+            },
+            '"' => unquote_double(&mut chars, &mut result)?,
+            '\\' => {
+                if let Some(c) = chars.next() {
+                    result.push(c);
+                }
+            }
+            c if c.is_whitespace() => break,
+            c => result.push(c),
+        }
+    }
+
+    Some(result)
+}
+
+fn unquote_double(chars: &mut Chars, result: &mut String) -> Option<()> {
+    loop {
+        match chars.next()? {
+            '"' => return Some(()),
+            '\\' => match chars.next()? {
+                c @ ('"' | '\\' | '$' | '`') => result.push(c),
+                c => {
+                    result.push('\\');
+                    result.push(c);
+                }
+            },
+            c => result.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn unquote_values() {
+        let data = [
+            ("", Some("")),
+            ("plain", Some("plain")),
+            ("\"double quoted\"", Some("double quoted")),
+            ("'single quoted'", Some("single quoted")),
+            (
+                "\"escaped \\\"quote\\\" \\$ \\` \\\\\"",
+                Some("escaped \"quote\" $ ` \\"),
+            ),
+            ("\"kept \\n\"", Some("kept \\n")),
+            ("'no \\\"escapes'", Some("no \\\"escapes")),
+            ("mixed\"quo\"'tes'", Some("mixedquotes")),
+            ("escaped\\ space", Some("escaped space")),
+            ("trailing   ", Some("trailing")),
+            ("\"unterminated", None),
+            ("'unterminated", None),
+        ];
+
+        for (value, expected) in &data {
+            assert_eq!(unquote(value).as_deref(), *expected, "{:?}", value);
+        }
+    }
+
+    #[test]
+    fn assignments() {
+        let data = [
+            ("NAME=Fedora", Some(("NAME", "Fedora"))),
+            ("VERSION_ID=\"35\"", Some(("VERSION_ID", "35"))),
+            ("EMPTY=", Some(("EMPTY", ""))),
+            ("=value", None),
+            ("NO_VALUE", None),
+            ("1KEY=value", None),
+            ("INVALID-KEY=value", None),
+            ("KEY=\"unterminated", None),
+        ];
+
+        for (line, expected) in &data {
+            let result = parse_assignment(line);
+            assert_eq!(
+                result.as_ref().map(|(k, v)| (k.as_str(), v.as_str())),
+                *expected
+            );
+        }
+    }
+
+    #[test]
+    fn fedora() {
+        let os_release = OsRelease::parse(include_str!("linux/tests/os-release-fedora-35"));
+        assert_eq!(os_release.name(), Some("Fedora Linux"));
+        assert_eq!(os_release.id(), Some("fedora"));
+        assert_eq!(os_release.id_like(), Vec::<&str>::new());
+        assert_eq!(os_release.version_id(), Some("35"));
+        assert_eq!(os_release.version_codename(), Some(""));
+        assert_eq!(os_release.variant(), Some("Workstation Edition"));
+        assert_eq!(os_release.variant_id(), Some("workstation"));
+        assert_eq!(
+            os_release.cpe_name(),
+            Some("cpe:/o:fedoraproject:fedora:35")
+        );
+        assert_eq!(os_release.home_url(), Some("https://fedoraproject.org/"));
+        assert_eq!(os_release.logo(), Some("fedora-logo-icon"));
+        assert_eq!(os_release.ansi_color(), Some("0;38;2;60;110;180"));
+    }
+
+    #[test]
+    fn ubuntu() {
+        let os_release = OsRelease::parse(include_str!("linux/tests/os-release-ubuntu"));
+        assert_eq!(os_release.name(), Some("Ubuntu"));
+        assert_eq!(os_release.id(), Some("ubuntu"));
+        assert_eq!(os_release.id_like(), vec!["debian"]);
+        assert_eq!(os_release.pretty_name(), Some("Ubuntu 18.10"));
+        assert_eq!(os_release.version(), Some("18.10 (Cosmic Cuttlefish)"));
+        assert_eq!(os_release.version_codename(), Some("cosmic"));
+        assert_eq!(os_release.get("UBUNTU_CODENAME"), Some("cosmic"));
+        assert_eq!(
+            os_release.privacy_policy_url(),
+            Some("https://www.ubuntu.com/legal/terms-and-policies/privacy-policy")
+        );
+    }
+
+    #[test]
+    fn comments_and_invalid_lines() {
+        let os_release = OsRelease::parse(
+            "# NAME=Commented\n\
+             \n\
+             NAME=Linux\n\
+             not an assignment\n\
+             \tID=linux\n\
+             IMAGE_ID=\"broken\n",
+        );
+        assert_eq!(os_release.name(), Some("Linux"));
+        assert_eq!(os_release.id(), Some("linux"));
+        assert_eq!(os_release.image_id(), None);
+        assert_eq!(os_release.iter().count(), 2);
+    }
+
+    #[test]
+    fn empty() {
+        let os_release = OsRelease::parse("");
+        assert!(os_release.is_empty());
+        assert_eq!(os_release, OsRelease::default());
+    }
+}
@@ -1,8 +1,92 @@
-use std::fmt::{self, Display, Formatter};
+// spell-checker:ignore amzn, archlinux, dragonflybsd, endeavouros, linuxmint, macosx, midnightbsd,
+// spell-checker:ignore opensuse, oraclelinux, redhatenterprise, rhel, sles
+
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use super::{registry, CustomType, Family, Kernel};
+
+/// All types except `Type::Other`, in the declaration order.
+const ALL: [Type; 32] = [
+    Type::Alpine,
+    Type::Amazon,
+    Type::Android,
+    Type::Arch,
+    Type::CentOS,
+    Type::Debian,
+    Type::DragonFly,
+    Type::Emscripten,
+    Type::EndeavourOS,
+    Type::Fedora,
+    Type::FreeBSD,
+    Type::Linux,
+    Type::Macos,
+    Type::Manjaro,
+    Type::Mariner,
+    Type::MidnightBSD,
+    Type::Mint,
+    Type::NetBSD,
+    Type::NixOS,
+    Type::OpenBSD,
+    Type::openSUSE,
+    Type::OracleLinux,
+    Type::Pop,
+    Type::Raspbian,
+    Type::Redhat,
+    Type::RedHatEnterprise,
+    Type::Redox,
+    Type::Solus,
+    Type::SUSE,
+    Type::Ubuntu,
+    Type::Unknown,
+    Type::Windows,
+];
+
+/// Names of all variants in the declaration order, as serialized by serde.
+#[cfg(feature = "serde")]
+const VARIANTS: [&str; 33] = [
+    "Alpine",
+    "Amazon",
+    "Android",
+    "Arch",
+    "CentOS",
+    "Debian",
+    "DragonFly",
+    "Emscripten",
+    "EndeavourOS",
+    "Fedora",
+    "FreeBSD",
+    "Linux",
+    "Macos",
+    "Manjaro",
+    "Mariner",
+    "MidnightBSD",
+    "Mint",
+    "NetBSD",
+    "NixOS",
+    "OpenBSD",
+    "openSUSE",
+    "OracleLinux",
+    "Other",
+    "Pop",
+    "Raspbian",
+    "Redhat",
+    "RedHatEnterprise",
+    "Redox",
+    "Solus",
+    "SUSE",
+    "Ubuntu",
+    "Unknown",
+    "Windows",
+];
 
 /// A list of supported operating system types.
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 #[allow(non_camel_case_types, clippy::upper_case_acronyms)]
 #[non_exhaustive]
 pub enum Type {
@@ -36,9 +120,9 @@ pub enum Type {
     Manjaro,
     /// Mariner (<https://en.wikipedia.org/wiki/CBL-Mariner>).
     Mariner,
-    /// Mint (<https://en.wikipedia.org/wiki/Linux_Mint>).
+    /// MidnightBSD (<https://en.wikipedia.org/wiki/MidnightBSD>).
     MidnightBSD,
-    /// MidnightBSD(<https://en.wikipedia.org/wiki/MidnightBSD>).
+    /// Mint (<https://en.wikipedia.org/wiki/Linux_Mint>).
     Mint,
     /// NetBSD (<https://en.wikipedia.org/wiki/NetBSD>).
     NetBSD,
@@ -50,11 +134,14 @@ pub enum Type {
     openSUSE,
     /// Oracle Linux (<https://en.wikipedia.org/wiki/Oracle_Linux>).
     OracleLinux,
+    /// An operating system without its own variant, usually registered with `Distribution`.
+    Other(CustomType),
     /// Pop!_OS (<https://en.wikipedia.org/wiki/Pop!_OS>)
     Pop,
     /// Raspberry Pi OS (<https://en.wikipedia.org/wiki/Raspberry_Pi_OS>).
     Raspbian,
-    /// Red Hat Linux (<https://en.wikipedia.org/wiki/Red_Hat_Linux>).
+    /// Red Hat Linux (<https://en.wikipedia.org/wiki/Red_Hat_Linux>). Red Hat Enterprise Linux
+    /// detected from its release files is reported as this type as well.
     Redhat,
     /// Red Hat Enterprise Linux (<https://en.wikipedia.org/wiki/Red_Hat_Enterprise_Linux>).
     RedHatEnterprise,
@@ -67,14 +154,342 @@ pub enum Type {
     /// Ubuntu (<https://en.wikipedia.org/wiki/Ubuntu_(operating_system)>).
     Ubuntu,
     /// Unknown operating system.
+    #[default]
     Unknown,
     /// Windows (<https://en.wikipedia.org/wiki/Microsoft_Windows>).
     Windows,
 }
 
-impl Default for Type {
-    fn default() -> Self {
-        Type::Unknown
+impl Type {
+    /// Returns all operating system types except `Type::Other`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Type;
+    ///
+    /// assert!(Type::all().contains(&Type::Ubuntu));
+    /// for os_type in Type::all() {
+    ///     println!("{}: {}", os_type.id(), os_type);
+    /// }
+    /// ```
+    pub fn all() -> &'static [Type] {
+        &ALL
+    }
+
+    /// Returns a stable lower-case identifier of the operating system type, suitable for storing
+    /// in databases or configuration files. Linux distributions use the `ID` of their `os-release`
+    /// file where possible. The identifier can be parsed back with `str::parse`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Type;
+    ///
+    /// assert_eq!("ubuntu", Type::Ubuntu.id());
+    /// assert_eq!("rhel", Type::RedHatEnterprise.id());
+    /// assert_eq!("macos", Type::Macos.id());
+    /// assert_eq!(Ok(Type::openSUSE), Type::openSUSE.id().parse());
+    /// ```
+    pub fn id(self) -> &'static str {
+        match self {
+            Type::Alpine => "alpine",
+            Type::Amazon => "amzn",
+            Type::Android => "android",
+            Type::Arch => "arch",
+            Type::CentOS => "centos",
+            Type::Debian => "debian",
+            Type::DragonFly => "dragonfly",
+            Type::Emscripten => "emscripten",
+            Type::EndeavourOS => "endeavouros",
+            Type::Fedora => "fedora",
+            Type::FreeBSD => "freebsd",
+            Type::Linux => "linux",
+            Type::Macos => "macos",
+            Type::Manjaro => "manjaro",
+            Type::Mariner => "mariner",
+            Type::MidnightBSD => "midnightbsd",
+            Type::Mint => "linuxmint",
+            Type::NetBSD => "netbsd",
+            Type::NixOS => "nixos",
+            Type::OpenBSD => "openbsd",
+            Type::openSUSE => "opensuse",
+            Type::OracleLinux => "ol",
+            Type::Other(custom) => custom.id(),
+            Type::Pop => "pop",
+            Type::Raspbian => "raspbian",
+            Type::Redhat => "redhat",
+            Type::RedHatEnterprise => "rhel",
+            Type::Redox => "redox",
+            Type::Solus => "solus",
+            Type::SUSE => "sles",
+            Type::Ubuntu => "ubuntu",
+            Type::Unknown => "unknown",
+            Type::Windows => "windows",
+        }
+    }
+
+    /// Returns the family this operating system type belongs to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Family, Type};
+    ///
+    /// assert_eq!(Family::Debian, Type::Ubuntu.family());
+    /// assert_eq!(Family::RedHat, Type::CentOS.family());
+    /// assert_eq!(Family::Independent, Type::Windows.family());
+    /// assert_eq!(Family::Unknown, Type::Linux.family());
+    /// ```
+    pub fn family(self) -> Family {
+        match self {
+            Type::Debian | Type::Mint | Type::Pop | Type::Raspbian | Type::Ubuntu => Family::Debian,
+            Type::Amazon
+            | Type::CentOS
+            | Type::Fedora
+            | Type::OracleLinux
+            | Type::Redhat
+            | Type::RedHatEnterprise => Family::RedHat,
+            Type::openSUSE | Type::SUSE => Family::SUSE,
+            Type::Arch | Type::EndeavourOS | Type::Manjaro => Family::Arch,
+            Type::Alpine => Family::Alpine,
+            Type::Android
+            | Type::DragonFly
+            | Type::Emscripten
+            | Type::FreeBSD
+            | Type::Macos
+            | Type::Mariner
+            | Type::MidnightBSD
+            | Type::NetBSD
+            | Type::NixOS
+            | Type::OpenBSD
+            | Type::Redox
+            | Type::Solus
+            | Type::Windows => Family::Independent,
+            Type::Linux | Type::Unknown => Family::Unknown,
+            Type::Other(custom) => custom.family(),
+        }
+    }
+
+    /// Returns the kind of kernel the operating system is built on. Custom types belonging to one
+    /// of the Linux distribution families are assumed to use the Linux kernel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Kernel, Type};
+    ///
+    /// assert_eq!(Kernel::Linux, Type::Ubuntu.kernel());
+    /// assert_eq!(Kernel::BSD, Type::FreeBSD.kernel());
+    /// assert_eq!(Kernel::XNU, Type::Macos.kernel());
+    /// assert_eq!(Kernel::NT, Type::Windows.kernel());
+    /// ```
+    pub fn kernel(self) -> Kernel {
+        match self {
+            Type::Alpine
+            | Type::Amazon
+            | Type::Android
+            | Type::Arch
+            | Type::CentOS
+            | Type::Debian
+            | Type::EndeavourOS
+            | Type::Fedora
+            | Type::Linux
+            | Type::Manjaro
+            | Type::Mariner
+            | Type::Mint
+            | Type::NixOS
+            | Type::openSUSE
+            | Type::OracleLinux
+            | Type::Pop
+            | Type::Raspbian
+            | Type::Redhat
+            | Type::RedHatEnterprise
+            | Type::Solus
+            | Type::SUSE
+            | Type::Ubuntu => Kernel::Linux,
+            Type::DragonFly | Type::FreeBSD | Type::MidnightBSD | Type::NetBSD | Type::OpenBSD => {
+                Kernel::BSD
+            }
+            Type::Macos => Kernel::XNU,
+            Type::Windows => Kernel::NT,
+            Type::Redox => Kernel::Redox,
+            Type::Emscripten => Kernel::Web,
+            Type::Unknown => Kernel::Unknown,
+            Type::Other(custom) => match custom.family() {
+                Family::Independent | Family::Unknown => Kernel::Unknown,
+                _ => Kernel::Linux,
+            },
+        }
+    }
+
+    /// Checks whether the operating system uses the Linux kernel. This includes Android.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Type;
+    ///
+    /// assert!(Type::Fedora.is_linux());
+    /// assert!(!Type::FreeBSD.is_linux());
+    /// ```
+    pub fn is_linux(self) -> bool {
+        self.kernel() == Kernel::Linux
+    }
+
+    /// Checks whether the operating system is one of the BSDs. macOS isn't considered a BSD.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Type;
+    ///
+    /// assert!(Type::OpenBSD.is_bsd());
+    /// assert!(!Type::Macos.is_bsd());
+    /// ```
+    pub fn is_bsd(self) -> bool {
+        self.kernel() == Kernel::BSD
+    }
+
+    /// Checks whether the operating system is Unix or Unix-like: Linux, the BSDs, macOS and Redox.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Type;
+    ///
+    /// assert!(Type::Macos.is_unix());
+    /// assert!(Type::NetBSD.is_unix());
+    /// assert!(!Type::Windows.is_unix());
+    /// ```
+    pub fn is_unix(self) -> bool {
+        matches!(
+            self.kernel(),
+            Kernel::Linux | Kernel::BSD | Kernel::XNU | Kernel::Redox
+        )
+    }
+
+    /// Checks whether the operating system is made primarily for desktop and laptop computers:
+    /// Windows, macOS and the Linux distributions that don't offer server editions (Linux Mint,
+    /// Pop!_OS, Manjaro, EndeavourOS and Solus).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Type;
+    ///
+    /// assert!(Type::Mint.is_desktop_os());
+    /// assert!(!Type::RedHatEnterprise.is_desktop_os());
+    /// assert!(!Type::Android.is_desktop_os());
+    /// ```
+    pub fn is_desktop_os(self) -> bool {
+        matches!(
+            self,
+            Type::EndeavourOS
+                | Type::Macos
+                | Type::Manjaro
+                | Type::Mint
+                | Type::Pop
+                | Type::Solus
+                | Type::Windows
+        )
+    }
+
+    /// Returns the address of the official website of the operating system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Type;
+    ///
+    /// assert_eq!(Some("https://www.debian.org"), Type::Debian.homepage());
+    /// assert_eq!(None, Type::Unknown.homepage());
+    /// ```
+    pub fn homepage(self) -> Option<&'static str> {
+        Some(match self {
+            Type::Alpine => "https://alpinelinux.org",
+            Type::Amazon => "https://aws.amazon.com/linux/",
+            Type::Android => "https://www.android.com",
+            Type::Arch => "https://archlinux.org",
+            Type::CentOS => "https://www.centos.org",
+            Type::Debian => "https://www.debian.org",
+            Type::DragonFly => "https://www.dragonflybsd.org",
+            Type::Emscripten => "https://emscripten.org",
+            Type::EndeavourOS => "https://endeavouros.com",
+            Type::Fedora => "https://fedoraproject.org",
+            Type::FreeBSD => "https://www.freebsd.org",
+            Type::Macos => "https://www.apple.com/macos/",
+            Type::Manjaro => "https://manjaro.org",
+            Type::Mariner => "https://github.com/microsoft/azurelinux",
+            Type::MidnightBSD => "https://www.midnightbsd.org",
+            Type::Mint => "https://linuxmint.com",
+            Type::NetBSD => "https://www.netbsd.org",
+            Type::NixOS => "https://nixos.org",
+            Type::OpenBSD => "https://www.openbsd.org",
+            Type::openSUSE => "https://www.opensuse.org",
+            Type::OracleLinux => "https://www.oracle.com/linux/",
+            Type::Pop => "https://pop.system76.com",
+            Type::Raspbian => "https://www.raspberrypi.com/software/",
+            Type::Redhat => "https://www.redhat.com",
+            Type::RedHatEnterprise => {
+                "https://www.redhat.com/en/technologies/linux-platforms/enterprise-linux"
+            }
+            Type::Redox => "https://www.redox-os.org",
+            Type::Solus => "https://getsol.us",
+            Type::SUSE => "https://www.suse.com/products/server/",
+            Type::Ubuntu => "https://ubuntu.com",
+            Type::Windows => "https://www.microsoft.com/windows",
+            Type::Linux | Type::Other(_) | Type::Unknown => return None,
+        })
+    }
+
+    /// Returns the address of the Wikipedia article about the operating system.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Type;
+    ///
+    /// assert_eq!(
+    ///     Some("https://en.wikipedia.org/wiki/FreeBSD"),
+    ///     Type::FreeBSD.wikipedia()
+    /// );
+    /// ```
+    pub fn wikipedia(self) -> Option<&'static str> {
+        Some(match self {
+            Type::Alpine => "https://en.wikipedia.org/wiki/Alpine_Linux",
+            Type::Amazon => "https://en.wikipedia.org/wiki/Amazon_Machine_Image#Amazon_Linux_AMI",
+            Type::Android => "https://en.wikipedia.org/wiki/Android_(operating_system)",
+            Type::Arch => "https://en.wikipedia.org/wiki/Arch_Linux",
+            Type::CentOS => "https://en.wikipedia.org/wiki/CentOS",
+            Type::Debian => "https://en.wikipedia.org/wiki/Debian",
+            Type::DragonFly => "https://en.wikipedia.org/wiki/DragonFly_BSD",
+            Type::Emscripten => "https://en.wikipedia.org/wiki/Emscripten",
+            Type::EndeavourOS => "https://en.wikipedia.org/wiki/EndeavourOS",
+            Type::Fedora => "https://en.wikipedia.org/wiki/Fedora_(operating_system)",
+            Type::FreeBSD => "https://en.wikipedia.org/wiki/FreeBSD",
+            Type::Linux => "https://en.wikipedia.org/wiki/Linux",
+            Type::Macos => "https://en.wikipedia.org/wiki/MacOS",
+            Type::Manjaro => "https://en.wikipedia.org/wiki/Manjaro",
+            Type::Mariner => "https://en.wikipedia.org/wiki/CBL-Mariner",
+            Type::MidnightBSD => "https://en.wikipedia.org/wiki/MidnightBSD",
+            Type::Mint => "https://en.wikipedia.org/wiki/Linux_Mint",
+            Type::NetBSD => "https://en.wikipedia.org/wiki/NetBSD",
+            Type::NixOS => "https://en.wikipedia.org/wiki/NixOS",
+            Type::OpenBSD => "https://en.wikipedia.org/wiki/OpenBSD",
+            Type::openSUSE => "https://en.wikipedia.org/wiki/OpenSUSE",
+            Type::OracleLinux => "https://en.wikipedia.org/wiki/Oracle_Linux",
+            Type::Pop => "https://en.wikipedia.org/wiki/Pop!_OS",
+            Type::Raspbian => "https://en.wikipedia.org/wiki/Raspberry_Pi_OS",
+            Type::Redhat => "https://en.wikipedia.org/wiki/Red_Hat_Linux",
+            Type::RedHatEnterprise => "https://en.wikipedia.org/wiki/Red_Hat_Enterprise_Linux",
+            Type::Redox => "https://en.wikipedia.org/wiki/Redox_(operating_system)",
+            Type::Solus => "https://en.wikipedia.org/wiki/Solus_(operating_system)",
+            Type::SUSE => "https://en.wikipedia.org/wiki/SUSE_Linux_Enterprise",
+            Type::Ubuntu => "https://en.wikipedia.org/wiki/Ubuntu_(operating_system)",
+            Type::Windows => "https://en.wikipedia.org/wiki/Microsoft_Windows",
+            Type::Other(_) | Type::Unknown => return None,
+        })
     }
 }
 
@@ -93,10 +508,145 @@ impl Display for Type {
             Type::Redhat => write!(f, "Red Hat Linux"),
             Type::RedHatEnterprise => write!(f, "Red Hat Enterprise Linux"),
             Type::SUSE => write!(f, "SUSE Linux Enterprise Server"),
+            Type::Other(custom) => write!(f, "{}", custom),
             _ => write!(f, "{:?}", self),
         }
     }
 }
+
+/// Parses an identifier returned by `Type::id` or one of the common aliases (for example, `el`,
+/// `osx` or `mint`), ignoring case. Identifiers of registered custom types are accepted too.
+///
+/// `redhat` is the identifier of `Type::Redhat`, while `rhel` and `el` give
+/// `Type::RedHatEnterprise`. Use `rhel` in a `Requirement` to match both.
+impl FromStr for Type {
+    type Err = TypeParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let id = s.trim().to_lowercase();
+        if let Some(&os_type) = ALL.iter().find(|t| t.id() == id) {
+            return Ok(os_type);
+        }
+
+        match id.as_str() {
+            "amazon" => Ok(Type::Amazon),
+            "archlinux" => Ok(Type::Arch),
+            "dragonflybsd" => Ok(Type::DragonFly),
+            "darwin" | "mac" | "macosx" | "osx" => Ok(Type::Macos),
+            "mint" => Ok(Type::Mint),
+            "opensuse-leap" | "opensuse-tumbleweed" => Ok(Type::openSUSE),
+            "oracle" | "oraclelinux" => Ok(Type::OracleLinux),
+            "el" | "redhatenterprise" => Ok(Type::RedHatEnterprise),
+            "pop-os" | "pop_os" => Ok(Type::Pop),
+            "suse" => Ok(Type::SUSE),
+            "win" => Ok(Type::Windows),
+            _ => registry::custom_type(&id)
+                .map(Type::Other)
+                .ok_or_else(|| TypeParseError { name: s.to_owned() }),
+        }
+    }
+}
+
+/// Custom types that weren't registered in this process are deserialized as `Type::Unknown`, as the
+/// strings of a custom type must live for the rest of the process.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Type {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_enum("Type", &VARIANTS, TypeVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct TypeVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::de::Visitor<'de> for TypeVisitor {
+    type Value = Type;
+
+    fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("an operating system type")
+    }
+
+    fn visit_enum<A: serde::de::EnumAccess<'de>>(self, data: A) -> Result<Type, A::Error> {
+        use serde::de::VariantAccess;
+
+        #[derive(serde::Deserialize)]
+        struct CustomId {
+            id: String,
+        }
+
+        match data.variant()? {
+            (Variant(Some(os_type)), variant) => variant.unit_variant().map(|()| os_type),
+            (Variant(None), variant) => {
+                let custom = variant.newtype_variant::<CustomId>()?;
+                Ok(registry::custom_type(&custom.id).map_or(Type::Unknown, Type::Other))
+            }
+        }
+    }
+}
+
+/// A variant name or index of `Type`, where `None` stands for `Type::Other`.
+#[cfg(feature = "serde")]
+struct Variant(Option<Type>);
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Variant {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct VariantVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for VariantVisitor {
+            type Value = Variant;
+
+            fn expecting(&self, f: &mut Formatter) -> fmt::Result {
+                f.write_str("a variant name or index")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, index: u64) -> Result<Variant, E> {
+                match VARIANTS.get(index as usize) {
+                    Some(name) => self.visit_str(name),
+                    None => Err(E::invalid_value(
+                        serde::de::Unexpected::Unsigned(index),
+                        &self,
+                    )),
+                }
+            }
+
+            fn visit_str<E: serde::de::Error>(self, name: &str) -> Result<Variant, E> {
+                if name == "Other" {
+                    return Ok(Variant(None));
+                }
+                ALL.iter()
+                    .find(|t| format!("{:?}", t) == name)
+                    .map(|&t| Variant(Some(t)))
+                    .ok_or_else(|| E::unknown_variant(name, &VARIANTS))
+            }
+
+            fn visit_bytes<E: serde::de::Error>(self, name: &[u8]) -> Result<Variant, E> {
+                match std::str::from_utf8(name) {
+                    Ok(name) => self.visit_str(name),
+                    Err(_) => Err(E::invalid_value(serde::de::Unexpected::Bytes(name), &self)),
+                }
+            }
+        }
+
+        deserializer.deserialize_identifier(VariantVisitor)
+    }
+}
+
+/// An error which can be returned when parsing a `Type`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeParseError {
+    name: String,
+}
+
+impl Display for TypeParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "unknown operating system type '{}'", self.name)
+    }
+}
+
+impl Error for TypeParseError {}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -140,10 +690,288 @@ mod tests {
             (Type::Ubuntu, "Ubuntu"),
             (Type::Unknown, "Unknown"),
             (Type::Windows, "Windows"),
+            (
+                Type::Other(CustomType::new("acme", "Acme Linux", Family::RedHat)),
+                "Acme Linux",
+            ),
         ];
 
         for (t, expected) in &data {
             assert_eq!(&t.to_string(), expected);
         }
     }
+
+    #[test]
+    fn family() {
+        let data = [
+            (Type::Mint, Family::Debian),
+            (Type::Raspbian, Family::Debian),
+            (Type::OracleLinux, Family::RedHat),
+            (Type::Amazon, Family::RedHat),
+            (Type::openSUSE, Family::SUSE),
+            (Type::Manjaro, Family::Arch),
+            (Type::Alpine, Family::Alpine),
+            (Type::NixOS, Family::Independent),
+            (Type::Macos, Family::Independent),
+            (Type::Unknown, Family::Unknown),
+            (
+                Type::Other(CustomType::new("acme", "Acme Linux", Family::Arch)),
+                Family::Arch,
+            ),
+        ];
+
+        for (t, expected) in &data {
+            assert_eq!(t.family(), *expected);
+        }
+    }
+
+    #[test]
+    fn all_is_complete() {
+        let variants = [
+            Type::Alpine,
+            Type::Amazon,
+            Type::Android,
+            Type::Arch,
+            Type::CentOS,
+            Type::Debian,
+            Type::DragonFly,
+            Type::Emscripten,
+            Type::EndeavourOS,
+            Type::Fedora,
+            Type::FreeBSD,
+            Type::Linux,
+            Type::Macos,
+            Type::Manjaro,
+            Type::Mariner,
+            Type::MidnightBSD,
+            Type::Mint,
+            Type::NetBSD,
+            Type::NixOS,
+            Type::OpenBSD,
+            Type::openSUSE,
+            Type::OracleLinux,
+            Type::Pop,
+            Type::Raspbian,
+            Type::Redhat,
+            Type::RedHatEnterprise,
+            Type::Redox,
+            Type::Solus,
+            Type::SUSE,
+            Type::Ubuntu,
+            Type::Unknown,
+            Type::Windows,
+        ];
+        for os_type in &variants {
+            // Adding a variant breaks this match, as a reminder to list it here, in `ALL` and in `VARIANTS`.
+            match os_type {
+                Type::Alpine
+                | Type::Amazon
+                | Type::Android
+                | Type::Arch
+                | Type::CentOS
+                | Type::Debian
+                | Type::DragonFly
+                | Type::Emscripten
+                | Type::EndeavourOS
+                | Type::Fedora
+                | Type::FreeBSD
+                | Type::Linux
+                | Type::Macos
+                | Type::Manjaro
+                | Type::Mariner
+                | Type::MidnightBSD
+                | Type::Mint
+                | Type::NetBSD
+                | Type::NixOS
+                | Type::OpenBSD
+                | Type::openSUSE
+                | Type::OracleLinux
+                | Type::Pop
+                | Type::Raspbian
+                | Type::Redhat
+                | Type::RedHatEnterprise
+                | Type::Redox
+                | Type::Solus
+                | Type::SUSE
+                | Type::Ubuntu
+                | Type::Unknown
+                | Type::Windows => assert!(ALL.contains(os_type), "{:?}", os_type),
+                Type::Other(_) => unreachable!(),
+            }
+        }
+        assert_eq!(variants.len(), ALL.len());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn variants() {
+        let names: Vec<_> = ALL.iter().map(|t| format!("{:?}", t)).collect();
+        let listed: Vec<_> = VARIANTS.iter().filter(|&&v| v != "Other").collect();
+        assert_eq!(listed, names.iter().collect::<Vec<_>>());
+        assert_eq!(VARIANTS.len(), ALL.len() + 1);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        for os_type in Type::all() {
+            let json = serde_json::to_string(os_type).unwrap();
+            assert_eq!(serde_json::from_str::<Type>(&json).unwrap(), *os_type);
+        }
+
+        const CUSTOM: CustomType = CustomType::new("type-serde", "Type Serde", Family::Arch);
+        crate::Distribution::new(Type::Other(CUSTOM)).register();
+        let json = serde_json::to_string(&Type::Other(CUSTOM)).unwrap();
+        assert_eq!(
+            json,
+            r#"{"Other":{"id":"type-serde","name":"Type Serde","family":"Arch"}}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<Type>(&json).unwrap(),
+            Type::Other(CUSTOM)
+        );
+
+        let json = r#"{"Other":{"id":"type-unregistered","name":"Unregistered","family":"Arch"}}"#;
+        assert_eq!(serde_json::from_str::<Type>(json).unwrap(), Type::Unknown);
+        assert!(serde_json::from_str::<Type>(r#""Nonexistent""#).is_err());
+        assert!(serde_json::from_str::<Type>(r#""Other""#).is_err());
+    }
+
+    #[test]
+    fn all() {
+        assert_eq!(Type::all().len(), ALL.len());
+        for (i, os_type) in Type::all().iter().enumerate() {
+            // The identifiers are unique.
+            assert!(
+                Type::all()[..i].iter().all(|t| t.id() != os_type.id()),
+                "{:?}",
+                os_type
+            );
+        }
+    }
+
+    #[cfg(feature = "schemars")]
+    #[test]
+    fn schema_variants() {
+        let schema = serde_json::to_value(schemars::schema_for!(Type)).unwrap();
+        let mut variants: Vec<_> = schema["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|variant| variant["const"].as_str())
+            .collect();
+        variants.sort_unstable();
+
+        let mut expected: Vec<_> = Type::all()
+            .iter()
+            .map(|t| {
+                serde_json::to_value(t)
+                    .unwrap()
+                    .as_str()
+                    .unwrap()
+                    .to_owned()
+            })
+            .collect();
+        expected.sort_unstable();
+        assert_eq!(variants, expected);
+    }
+
+    #[test]
+    fn kernels() {
+        let data = [
+            (Type::Ubuntu, Kernel::Linux, true, false, true, false),
+            (Type::Android, Kernel::Linux, true, false, true, false),
+            (Type::Mint, Kernel::Linux, true, false, true, true),
+            (Type::FreeBSD, Kernel::BSD, false, true, true, false),
+            (Type::DragonFly, Kernel::BSD, false, true, true, false),
+            (Type::Macos, Kernel::XNU, false, false, true, true),
+            (Type::Windows, Kernel::NT, false, false, false, true),
+            (Type::Redox, Kernel::Redox, false, false, true, false),
+            (Type::Emscripten, Kernel::Web, false, false, false, false),
+            (Type::Unknown, Kernel::Unknown, false, false, false, false),
+            (
+                Type::Other(CustomType::new("acme", "Acme Linux", Family::RedHat)),
+                Kernel::Linux,
+                true,
+                false,
+                true,
+                false,
+            ),
+            (
+                Type::Other(CustomType::new("acme", "Acme OS", Family::Independent)),
+                Kernel::Unknown,
+                false,
+                false,
+                false,
+                false,
+            ),
+        ];
+
+        for (os_type, kernel, linux, bsd, unix, desktop) in &data {
+            assert_eq!(os_type.kernel(), *kernel, "{:?}", os_type);
+            assert_eq!(os_type.is_linux(), *linux, "{:?}", os_type);
+            assert_eq!(os_type.is_bsd(), *bsd, "{:?}", os_type);
+            assert_eq!(os_type.is_unix(), *unix, "{:?}", os_type);
+            assert_eq!(os_type.is_desktop_os(), *desktop, "{:?}", os_type);
+        }
+    }
+
+    #[test]
+    fn links() {
+        for os_type in Type::all() {
+            if let Some(homepage) = os_type.homepage() {
+                assert!(homepage.starts_with("https://"), "{}", homepage);
+            }
+            match os_type.wikipedia() {
+                Some(link) => assert!(link.starts_with("https://en.wikipedia.org/wiki/")),
+                None => assert_eq!(*os_type, Type::Unknown),
+            }
+        }
+        assert_eq!(Type::Linux.homepage(), None);
+        assert_eq!(
+            Type::Other(CustomType::new("acme", "Acme Linux", Family::RedHat)).wikipedia(),
+            None
+        );
+    }
+
+    #[test]
+    fn id_round_trip() {
+        for os_type in Type::all() {
+            assert_eq!(os_type.id().parse(), Ok(*os_type));
+            assert_eq!(os_type.id().to_uppercase().parse(), Ok(*os_type));
+            assert_eq!(os_type.id(), os_type.id().to_lowercase());
+        }
+
+        let custom = CustomType::new("os-type-test", "OS Type Test", Family::Debian);
+        crate::Distribution::new(Type::Other(custom)).register();
+        assert_eq!(Type::Other(custom).id().parse(), Ok(Type::Other(custom)));
+    }
+
+    #[test]
+    fn aliases() {
+        let data = [
+            ("el", Type::RedHatEnterprise),
+            ("rhel", Type::RedHatEnterprise),
+            // Kept apart from RHEL, so that `Type::Redhat` round-trips through its identifier.
+            ("redhat", Type::Redhat),
+            ("osx", Type::Macos),
+            ("OSX", Type::Macos),
+            ("mint", Type::Mint),
+            ("amazon", Type::Amazon),
+            ("suse", Type::SUSE),
+            ("opensuse-leap", Type::openSUSE),
+            (" ubuntu ", Type::Ubuntu),
+        ];
+
+        for (s, expected) in &data {
+            assert_eq!(s.parse::<Type>(), Ok(*expected), "{}", s);
+        }
+    }
+
+    #[test]
+    fn parse_error() {
+        let error = "plan9".parse::<Type>().unwrap_err();
+        assert_eq!(error.to_string(), "unknown operating system type 'plan9'");
+        assert!("".parse::<Type>().is_err());
+    }
 }
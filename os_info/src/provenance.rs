@@ -0,0 +1,473 @@
+// spell-checker:ignore getconf, osproductversion, sysctl
+
+use std::fmt::{self, Display, Formatter};
+
+use super::{DetectionError, Info, Type, Version};
+
+/// Kind of a source consulted while detecting the operating system.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Source {
+    /// The `lsb_release -a` command.
+    LsbRelease,
+    /// The `/etc/lsb-release` file. When detecting the current system, the `os-release` file is
+    /// interpreted the way the `lsb_release` command does if `/etc/lsb-release` doesn't exist.
+    LsbReleaseFile,
+    /// A distribution-specific release file such as `/etc/redhat-release`.
+    ReleaseFile,
+    /// The `os-release` file (`/etc/os-release` or `/usr/lib/os-release`).
+    OsRelease,
+    /// The `uname` system call.
+    Uname,
+    /// An external command other than `lsb_release`, for example, `sw_vers` on macOS.
+    Command,
+    /// The `getconf LONG_BIT` command (`sysctl` on NetBSD and OpenBSD) used to determine the
+    /// bitness.
+    Getconf,
+    /// The `sysctl` system call, for example, `kern.osproductversion` on macOS.
+    Sysctl,
+    /// An operating system API, for example, `RtlGetVersion` on Windows.
+    SystemApi,
+    /// The target the crate was compiled for.
+    Target,
+    /// The default used when no other source provided an answer.
+    Fallback,
+    /// An override set with `testing::override_with` or the `OS_INFO_OVERRIDE` environment
+    /// variable (with the `testing` feature).
+    Override,
+}
+
+/// Outcome of consulting a source.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum SourceStatus {
+    /// The file or command doesn't exist.
+    Missing,
+    /// The source exists, but couldn't be read or executed successfully.
+    Failed,
+    /// The source was read, but its content wasn't recognized.
+    Unrecognized,
+    /// The source was read and parsed.
+    Parsed,
+}
+
+/// How much the detected type and version can be trusted.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum Confidence {
+    /// Nothing has been detected, the result is a default.
+    #[default]
+    Unknown,
+    /// The result is a guess, for example, the type is a generic `Linux` or the content of the
+    /// source wasn't recognized.
+    Low,
+    /// The type is known, but the version isn't.
+    Medium,
+    /// Both the type and the version are known.
+    High,
+}
+
+/// A single source consulted while detecting the operating system.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SourceRecord {
+    pub(crate) source: Source,
+    pub(crate) location: String,
+    pub(crate) status: SourceStatus,
+    pub(crate) selected: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) error: Option<DetectionError>,
+}
+
+impl SourceRecord {
+    /// Returns the kind of the source. See `Source` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info;
+    ///
+    /// for record in os_info::get().provenance().records() {
+    ///     println!("{:?}", record.source());
+    /// }
+    /// ```
+    pub fn source(&self) -> Source {
+        self.source
+    }
+
+    /// Returns the location of the source: a file path, a command line or a function name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info;
+    ///
+    /// for record in os_info::get().provenance().records() {
+    ///     println!("{}", record.location());
+    /// }
+    /// ```
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// Returns the outcome of consulting the source. See `SourceStatus` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info;
+    ///
+    /// for record in os_info::get().provenance().records() {
+    ///     println!("{:?}", record.status());
+    /// }
+    /// ```
+    pub fn status(&self) -> SourceStatus {
+        self.status
+    }
+
+    /// Returns `true` if the operating system type and version were taken from this source.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info;
+    ///
+    /// let info = os_info::get();
+    /// let selected = info.provenance().records().iter().filter(|r| r.is_selected());
+    /// assert!(selected.count() <= 1);
+    /// ```
+    pub fn is_selected(&self) -> bool {
+        self.selected
+    }
+
+    /// Returns the error that occurred while consulting the source, if any. See `DetectionError`
+    /// for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info;
+    ///
+    /// for record in os_info::get().provenance().records() {
+    ///     if let Some(error) = record.error() {
+    ///         println!("{}: {}", record.location(), error);
+    ///     }
+    /// }
+    /// ```
+    pub fn error(&self) -> Option<&DetectionError> {
+        self.error.as_ref()
+    }
+}
+
+impl Display for SourceRecord {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let status = match self.status {
+            SourceStatus::Missing => "missing",
+            SourceStatus::Failed => "failed",
+            SourceStatus::Unrecognized => "unrecognized",
+            SourceStatus::Parsed => "parsed",
+        };
+        let marker = if self.selected { '*' } else { ' ' };
+        write!(f, "{} {}: {}", marker, self.location, status)
+    }
+}
+
+/// Describes how the operating system information was detected: every source that was consulted
+/// in order, which of them provided the result, and how confident the result is.
+///
+/// The `Display` implementation produces a detection trace suitable for bug reports.
+///
+/// # Examples
+///
+/// ```
+/// use os_info;
+///
+/// let info = os_info::get();
+/// println!("Detected {} with {:?} confidence:", info, info.provenance().confidence());
+/// println!("{}", info.provenance());
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Provenance {
+    pub(crate) records: Vec<SourceRecord>,
+    pub(crate) confidence: Confidence,
+}
+
+impl Provenance {
+    /// Returns all consulted sources in the order they were consulted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Info;
+    ///
+    /// let info = Info::unknown();
+    /// assert!(info.provenance().records().is_empty());
+    /// ```
+    pub fn records(&self) -> &[SourceRecord] {
+        &self.records
+    }
+
+    /// Returns the source that provided the operating system type and version, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Info;
+    ///
+    /// let info = Info::unknown();
+    /// assert_eq!(None, info.provenance().selected());
+    /// ```
+    pub fn selected(&self) -> Option<&SourceRecord> {
+        self.records.iter().find(|r| r.selected)
+    }
+
+    /// Returns the confidence of the detection result. See `Confidence` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Confidence, Info};
+    ///
+    /// let info = Info::unknown();
+    /// assert_eq!(Confidence::Unknown, info.provenance().confidence());
+    /// ```
+    pub fn confidence(&self) -> Confidence {
+        self.confidence
+    }
+
+    /// Records a consulted source.
+    pub(crate) fn record<S: Into<String>>(
+        &mut self,
+        source: Source,
+        location: S,
+        status: SourceStatus,
+    ) {
+        self.records.push(SourceRecord {
+            source,
+            location: location.into(),
+            status,
+            selected: false,
+            error: None,
+        });
+    }
+
+    /// Records a source that couldn't be used because of the given error.
+    pub(crate) fn record_error<S: Into<String>>(
+        &mut self,
+        source: Source,
+        location: S,
+        error: DetectionError,
+    ) {
+        let status = if error.is_missing() {
+            SourceStatus::Missing
+        } else if let DetectionError::UnparseableContent { .. } = error {
+            SourceStatus::Unrecognized
+        } else {
+            SourceStatus::Failed
+        };
+        self.record(source, location, status);
+        if let Some(record) = self.records.last_mut() {
+            record.error = Some(error);
+        }
+    }
+
+    /// Records that the operating system type is implied by the compilation target.
+    #[cfg(any(
+        target_os = "android",
+        target_os = "dragonfly",
+        target_os = "emscripten",
+        target_os = "freebsd",
+        target_os = "macos",
+        target_os = "netbsd",
+        target_os = "openbsd",
+        target_os = "redox",
+        target_os = "windows"
+    ))]
+    pub(crate) fn record_target(&mut self) {
+        self.record(
+            Source::Target,
+            format!("target_os = {:?}", std::env::consts::OS),
+            SourceStatus::Parsed,
+        );
+    }
+
+    /// Marks the last recorded source as the one the given information was taken from and
+    /// determines the confidence of the result.
+    pub(crate) fn select(&mut self, info: &Info) {
+        let record = match self.records.last_mut() {
+            Some(record) => record,
+            None => return,
+        };
+        record.selected = true;
+
+        self.confidence = if record.source == Source::Fallback {
+            Confidence::Unknown
+        } else if record.status != SourceStatus::Parsed
+            || matches!(info.os_type, Type::Linux | Type::Unknown)
+        {
+            Confidence::Low
+        } else if info.version == Version::Unknown {
+            Confidence::Medium
+        } else {
+            Confidence::High
+        };
+    }
+
+    /// Checks whether the operating system has been detected. Otherwise returns the most relevant
+    /// error: a failure of an existing source takes precedence over a missing source.
+    pub(crate) fn result(&self) -> Result<(), DetectionError> {
+        let detected = self
+            .selected()
+            .is_some_and(|r| r.status == SourceStatus::Parsed && r.source != Source::Fallback);
+        if detected {
+            return Ok(());
+        }
+
+        let errors = || self.records.iter().filter_map(|r| r.error.as_ref());
+        Err(errors()
+            .find(|e| !e.is_missing())
+            .or_else(|| errors().next())
+            .cloned()
+            .unwrap_or(DetectionError::Undetected))
+    }
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "confidence: {:?}", self.confidence)?;
+        for record in &self.records {
+            write!(f, "\n{}", record)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn default() {
+        let provenance = Provenance::default();
+        assert!(provenance.records().is_empty());
+        assert_eq!(None, provenance.selected());
+        assert_eq!(Confidence::Unknown, provenance.confidence());
+    }
+
+    #[test]
+    fn confidence() {
+        let ubuntu = Info {
+            os_type: Type::Ubuntu,
+            version: Version::Semantic(22, 4, 0),
+            ..Default::default()
+        };
+        let data = [
+            (
+                Source::ReleaseFile,
+                SourceStatus::Parsed,
+                ubuntu.clone(),
+                Confidence::High,
+            ),
+            (
+                Source::ReleaseFile,
+                SourceStatus::Parsed,
+                Info::with_type(Type::Ubuntu),
+                Confidence::Medium,
+            ),
+            (
+                Source::LsbRelease,
+                SourceStatus::Parsed,
+                Info::with_type(Type::Linux),
+                Confidence::Low,
+            ),
+            (
+                Source::ReleaseFile,
+                SourceStatus::Unrecognized,
+                ubuntu,
+                Confidence::Low,
+            ),
+            (
+                Source::Fallback,
+                SourceStatus::Parsed,
+                Info::with_type(Type::Linux),
+                Confidence::Unknown,
+            ),
+        ];
+
+        for (source, status, info, expected) in &data {
+            let mut provenance = Provenance::default();
+            provenance.record(Source::LsbRelease, "lsb_release -a", SourceStatus::Missing);
+            provenance.record(*source, "source", *status);
+            provenance.select(info);
+
+            assert_eq!(provenance.confidence(), *expected);
+            assert_eq!(provenance.selected().map(|r| r.location()), Some("source"));
+        }
+    }
+
+    #[test]
+    fn result() {
+        let not_found = std::io::Error::from(std::io::ErrorKind::NotFound);
+        let missing = DetectionError::command("lsb_release -a", &not_found);
+        let unparseable = DetectionError::UnparseableContent {
+            location: "/etc/os-release".to_owned(),
+            message: "unknown NAME 'Debian'".to_owned(),
+        };
+
+        let mut provenance = Provenance::default();
+        assert_eq!(provenance.result(), Err(DetectionError::Undetected));
+
+        provenance.record_error(Source::LsbRelease, "lsb_release -a", missing.clone());
+        assert_eq!(provenance.records()[0].status(), SourceStatus::Missing);
+        assert_eq!(provenance.result(), Err(missing.clone()));
+
+        provenance.record_error(Source::ReleaseFile, "/etc/os-release", unparseable.clone());
+        assert_eq!(provenance.records()[1].status(), SourceStatus::Unrecognized);
+        provenance.select(&Info::with_type(Type::OracleLinux));
+        assert_eq!(provenance.result(), Err(unparseable));
+
+        let mut provenance = Provenance::default();
+        provenance.record_error(Source::LsbRelease, "lsb_release -a", missing);
+        provenance.record(Source::ReleaseFile, "/etc/os-release", SourceStatus::Parsed);
+        provenance.select(&Info::with_type(Type::Fedora));
+        assert_eq!(provenance.result(), Ok(()));
+
+        let mut provenance = Provenance::default();
+        provenance.record(Source::Fallback, "generic Linux", SourceStatus::Parsed);
+        provenance.select(&Info::with_type(Type::Linux));
+        assert_eq!(provenance.result(), Err(DetectionError::Undetected));
+    }
+
+    #[test]
+    fn display() {
+        let mut provenance = Provenance::default();
+        provenance.record(Source::LsbRelease, "lsb_release -a", SourceStatus::Missing);
+        provenance.record(
+            Source::ReleaseFile,
+            "/etc/mariner-release",
+            SourceStatus::Missing,
+        );
+        provenance.record(Source::ReleaseFile, "/etc/os-release", SourceStatus::Parsed);
+        provenance.select(&Info {
+            os_type: Type::Fedora,
+            version: Version::Semantic(35, 0, 0),
+            ..Default::default()
+        });
+
+        assert_eq!(
+            provenance.to_string(),
+            "confidence: High\n  lsb_release -a: missing\n  /etc/mariner-release: missing\n\
+             * /etc/os-release: parsed"
+        );
+    }
+}
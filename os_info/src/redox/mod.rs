@@ -1,34 +1,45 @@
 // spell-checker:ignore uname
 
-use std::{fs::File, io::Read};
+use std::{fs::File, io::Read, path::PathBuf};
 
 use log::{error, trace};
 
-use crate::{Bitness, Info, Type, Version};
+use crate::{
+    Bitness, DetectionError, Detector, Info, Provenance, Source, SourceStatus, Type, Version,
+};
 
 const UNAME_FILE: &str = "sys:uname";
 
-pub fn current_platform() -> Info {
-    trace!("redox::current_platform is called");
+pub fn current_platform(detector: &Detector) -> Info {
+    trace!("redox::current_platform({:?}) is called", detector);
 
-    let version = get_version()
+    let mut provenance = Provenance::default();
+    let version = get_version(&mut provenance)
         .map(Version::from_string)
         .unwrap_or_else(|| Version::Unknown);
-    let info = Info {
+    if version == Version::Unknown {
+        provenance.record_target();
+    }
+
+    let mut info = Info {
         os_type: Type::Redox,
         version,
         bitness: Bitness::Unknown,
         ..Default::default()
     };
+    provenance.select(&info);
+    info.provenance = provenance;
     trace!("Returning {:?}", info);
     info
 }
 
-fn get_version() -> Option<String> {
+fn get_version(provenance: &mut Provenance) -> Option<String> {
     let mut file = match File::open(UNAME_FILE) {
         Ok(file) => file,
         Err(e) => {
             error!("Unable to open {} file: {:?}", UNAME_FILE, e);
+            let error = DetectionError::file(PathBuf::from(UNAME_FILE), &e);
+            provenance.record_error(Source::ReleaseFile, UNAME_FILE, error);
             return None;
         }
     };
@@ -36,8 +47,11 @@ fn get_version() -> Option<String> {
     let mut version = String::new();
     if let Err(e) = file.read_to_string(&mut version) {
         error!("Unable to read {} file: {:?}", UNAME_FILE, e);
+        let error = DetectionError::file(PathBuf::from(UNAME_FILE), &e);
+        provenance.record_error(Source::ReleaseFile, UNAME_FILE, error);
         return None;
     }
+    provenance.record(Source::ReleaseFile, UNAME_FILE, SourceStatus::Parsed);
     Some(version)
 }
 
@@ -48,7 +62,7 @@ mod tests {
 
     #[test]
     fn os_type() {
-        let version = current_platform();
+        let version = current_platform(&Detector::default());
         assert_eq!(Type::Redox, version.os_type());
     }
 }
@@ -0,0 +1,240 @@
+use std::{
+    collections::BTreeSet,
+    sync::{Mutex, PoisonError, RwLock, RwLockReadGuard},
+};
+
+use log::trace;
+
+use super::{CustomType, OsRelease, Type};
+
+/// Distributions registered at runtime, in the order of registration.
+static REGISTRY: RwLock<Vec<Distribution>> = RwLock::new(Vec::new());
+
+/// Strings of custom types constructed at runtime.
+static INTERNED: Mutex<BTreeSet<&'static str>> = Mutex::new(BTreeSet::new());
+
+/// Mappings from the values reported by a Linux distribution to an operating system type,
+/// registered at runtime.
+///
+/// Registered distributions take precedence over the built-in ones and over the distributions
+/// registered earlier. All values are compared case-insensitively.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::{CustomType, Distribution, Family, Type};
+///
+/// const ACME: CustomType = CustomType::new("acme", "Acme Linux", Family::RedHat);
+///
+/// Distribution::new(Type::Other(ACME))
+///     .name("Acme Enterprise Linux")
+///     .id("acme")
+///     .distributor_id("AcmeEnterprise")
+///     .register();
+///
+/// // A rebranded distribution can also be mapped to one of the existing types.
+/// Distribution::new(Type::RedHatEnterprise)
+///     .name("Example Server")
+///     .register();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Distribution {
+    os_type: Type,
+    names: Vec<String>,
+    ids: Vec<String>,
+    distributor_ids: Vec<String>,
+}
+
+impl Distribution {
+    /// Constructs a distribution of the given type without any mappings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Distribution, Type};
+    ///
+    /// let distribution = Distribution::new(Type::Ubuntu);
+    /// ```
+    pub fn new(os_type: Type) -> Self {
+        Self {
+            os_type,
+            names: Vec::new(),
+            ids: Vec::new(),
+            distributor_ids: Vec::new(),
+        }
+    }
+
+    /// Adds a name reported in the `NAME` field of the `os-release` file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Distribution, Type};
+    ///
+    /// let distribution = Distribution::new(Type::Ubuntu).name("Ubuntu Custom");
+    /// ```
+    pub fn name<S: Into<String>>(mut self, name: S) -> Self {
+        self.names.push(name.into());
+        self
+    }
+
+    /// Adds an identifier reported in the `ID` field of the `os-release` file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Distribution, Type};
+    ///
+    /// let distribution = Distribution::new(Type::Ubuntu).id("ubuntu-custom");
+    /// ```
+    pub fn id<S: Into<String>>(mut self, id: S) -> Self {
+        self.ids.push(id.into());
+        self
+    }
+
+    /// Adds a distributor ID reported by the `lsb_release` command or in the `DISTRIB_ID` field of
+    /// the `/etc/lsb-release` file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Distribution, Type};
+    ///
+    /// let distribution = Distribution::new(Type::Ubuntu).distributor_id("UbuntuCustom");
+    /// ```
+    pub fn distributor_id<S: Into<String>>(mut self, distributor_id: S) -> Self {
+        self.distributor_ids.push(distributor_id.into());
+        self
+    }
+
+    /// Registers the distribution for all subsequent detections in this process.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Distribution, Type};
+    ///
+    /// Distribution::new(Type::Ubuntu).name("Ubuntu Custom").register();
+    /// ```
+    pub fn register(self) {
+        trace!("Registering {:?}", self);
+        REGISTRY
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(self);
+    }
+}
+
+/// Returns the type registered for the `NAME` or, failing that, the `ID` of the `os-release`
+/// file.
+pub(crate) fn from_os_release(os_release: &OsRelease) -> Option<Type> {
+    let by = |value: Option<&str>, values: fn(&Distribution) -> &[String]| {
+        value.and_then(|value| find(|d| contains(values(d), value)))
+    };
+    by(os_release.name(), |d| &d.names).or_else(|| by(os_release.id(), |d| &d.ids))
+}
+
+/// Returns the type registered for the distributor ID.
+pub(crate) fn from_distributor_id(distributor_id: &str) -> Option<Type> {
+    find(|d| contains(&d.distributor_ids, distributor_id))
+}
+
+/// Returns the registered custom type with the given identifier.
+pub(crate) fn custom_type(id: &str) -> Option<CustomType> {
+    read().iter().rev().find_map(|d| match d.os_type {
+        Type::Other(custom) if custom.id().eq_ignore_ascii_case(id) => Some(custom),
+        _ => None,
+    })
+}
+
+/// Returns a string that lives for the rest of the process, allocating it only once.
+pub(crate) fn intern(value: String) -> &'static str {
+    let mut interned = INTERNED.lock().unwrap_or_else(PoisonError::into_inner);
+    match interned.get(value.as_str()) {
+        Some(value) => value,
+        None => {
+            let value = Box::leak(value.into_boxed_str());
+            interned.insert(value);
+            value
+        }
+    }
+}
+
+fn find<F: Fn(&Distribution) -> bool>(predicate: F) -> Option<Type> {
+    read()
+        .iter()
+        .rev()
+        .find(|d| predicate(d))
+        .map(|d| d.os_type)
+}
+
+fn contains(values: &[String], value: &str) -> bool {
+    values.iter().any(|v| v.eq_ignore_ascii_case(value))
+}
+
+fn read() -> RwLockReadGuard<'static, Vec<Distribution>> {
+    REGISTRY.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Family;
+    use pretty_assertions::assert_eq;
+
+    // The registry is shared by all tests, so every test uses its own names.
+
+    #[test]
+    fn os_release() {
+        const CUSTOM: CustomType = CustomType::new("registry-test", "Registry Test", Family::Arch);
+        Distribution::new(Type::Other(CUSTOM))
+            .name("Registry Test Linux")
+            .id("registry-test")
+            .register();
+
+        let data = [
+            (
+                "NAME=\"Registry Test Linux\"\nID=other",
+                Some(Type::Other(CUSTOM)),
+            ),
+            ("NAME=\"registry test linux\"", Some(Type::Other(CUSTOM))),
+            ("NAME=Other\nID=registry-test", Some(Type::Other(CUSTOM))),
+            ("NAME=Other\nID=other", None),
+        ];
+
+        for (content, expected) in &data {
+            let os_release = OsRelease::parse(content);
+            assert_eq!(from_os_release(&os_release), *expected, "{}", content);
+        }
+        assert_eq!(custom_type("REGISTRY-TEST"), Some(CUSTOM));
+    }
+
+    #[test]
+    fn distributor_id() {
+        Distribution::new(Type::Fedora)
+            .distributor_id("RegistryFedora")
+            .register();
+        assert_eq!(from_distributor_id("registryfedora"), Some(Type::Fedora));
+        assert_eq!(from_distributor_id("RegistryMissing"), None);
+    }
+
+    #[test]
+    fn precedence() {
+        Distribution::new(Type::Debian)
+            .name("Registry Precedence")
+            .register();
+        Distribution::new(Type::Ubuntu)
+            .name("Registry Precedence")
+            .register();
+        let os_release = OsRelease::parse("NAME=\"Registry Precedence\"");
+        assert_eq!(from_os_release(&os_release), Some(Type::Ubuntu));
+    }
+
+    #[test]
+    fn interned() {
+        let first = intern("registry-interned".to_owned());
+        let second = intern(String::from("registry-interned"));
+        assert_eq!(first, "registry-interned");
+        assert_eq!(first.as_ptr(), second.as_ptr());
+    }
+}
@@ -0,0 +1,674 @@
+// spell-checker:ignore rhel, sles, opensuse, endeavouros, oraclelinux, midnightbsd, linuxmint
+
+use std::{
+    cmp::Ordering,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use super::{Family, Info, Type, Version};
+
+/// A predicate over operating system information, such as `ubuntu >= 20.04 || rhel >= 8`.
+///
+/// Requirements are usually parsed from strings with the following syntax:
+///
+/// - `ubuntu`: the operating system type, using the identifiers returned by `Type::id` (`ubuntu`,
+///   `rhel`, `macos`, `windows`, ...) or their aliases. Registered custom types are referred to
+///   by their identifiers. `rhel` also matches `Type::Redhat`, which is how Red Hat Enterprise
+///   Linux is detected from its release files.
+/// - `ubuntu 22.04` or `ubuntu = 22.04`: the type and the version, where only the given version
+///   components are compared, so `rhel 8` matches RHEL 8.6.
+/// - `ubuntu >= 20.04`: the type and a version comparison (`<`, `<=`, `>`, `>=`, `=`, `!=`).
+///   Several comparisons can be separated by commas: `ubuntu >= 20.04, < 24.04`.
+/// - `ubuntu 20.04..24.04`: a half-open version range.
+/// - `family:debian`: the family of the operating system. See `Family` for details.
+/// - `!`, `&&`, `||` and parentheses combine requirements.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::{Info, Requirement, Type};
+///
+/// let requirement: Requirement = "ubuntu >= 20.04 || family:redhat".parse().unwrap();
+/// assert!(Info::with_type(Type::CentOS).satisfies(&requirement));
+/// assert!(!Info::with_type(Type::Windows).satisfies(&requirement));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Requirement {
+    /// Matches the operating system type if all version constraints are satisfied.
+    Type(Type, Vec<Constraint>),
+    /// Matches the operating system family.
+    Family(Family),
+    /// Matches if the inner requirement doesn't.
+    Not(Box<Requirement>),
+    /// Matches if all of the inner requirements match.
+    All(Vec<Requirement>),
+    /// Matches if any of the inner requirements match.
+    Any(Vec<Requirement>),
+}
+
+impl Requirement {
+    /// Checks whether the operating system information satisfies the requirement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Info, Requirement};
+    ///
+    /// let requirement: Requirement = "!windows".parse().unwrap();
+    /// assert!(requirement.matches(&Info::unknown()));
+    /// ```
+    pub fn matches(&self, info: &Info) -> bool {
+        match self {
+            Requirement::Type(os_type, constraints) => {
+                same_type(*os_type, info.os_type())
+                    && constraints.iter().all(|c| c.matches(info.version()))
+            }
+            Requirement::Family(family) => info.family() == *family,
+            Requirement::Not(requirement) => !requirement.matches(info),
+            Requirement::All(requirements) => requirements.iter().all(|r| r.matches(info)),
+            Requirement::Any(requirements) => requirements.iter().any(|r| r.matches(info)),
+        }
+    }
+}
+
+impl FromStr for Requirement {
+    type Err = RequirementParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let tokens = tokenize(s)?;
+        let mut parser = Parser {
+            tokens: &tokens,
+            position: 0,
+            end: s.len(),
+        };
+        let requirement = parser.parse_any()?;
+        match parser.peek() {
+            None => Ok(requirement),
+            Some((position, token)) => Err(RequirementParseError::new(
+                position,
+                format!("unexpected {}", token),
+            )),
+        }
+    }
+}
+
+/// A comparison of the operating system version with the given one.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Constraint {
+    operator: Operator,
+    version: Version,
+}
+
+impl Constraint {
+    /// Constructs a new constraint.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Constraint, Operator, Version};
+    ///
+    /// let constraint = Constraint::new(Operator::GreaterOrEqual, Version::Extended(vec![20, 4], None));
+    /// assert!(constraint.matches(&Version::Semantic(22, 4, 0)));
+    /// ```
+    pub fn new(operator: Operator, version: Version) -> Self {
+        Self { operator, version }
+    }
+
+    /// Returns the comparison operator.
+    pub fn operator(&self) -> Operator {
+        self.operator
+    }
+
+    /// Returns the version the operating system version is compared with.
+    pub fn version(&self) -> &Version {
+        &self.version
+    }
+
+    /// Checks whether the given version satisfies the constraint. Versions without numeric
+    /// components, such as `Version::Unknown`, never do.
+    ///
+    /// `Operator::Equal` and `Operator::NotEqual` only compare the components present in the
+    /// constraint version, so `8` matches `8.6`, but `8.0.0` doesn't.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::{Constraint, Operator, Version};
+    ///
+    /// let constraint = Constraint::new(Operator::Equal, Version::Extended(vec![8], None));
+    /// assert!(constraint.matches(&Version::from_string("8.6")));
+    /// assert!(!constraint.matches(&Version::from_string("9.0")));
+    /// assert!(!constraint.matches(&Version::Unknown));
+    /// ```
+    pub fn matches(&self, version: &Version) -> bool {
+        if version.major().is_none() {
+            return false;
+        }
+
+        match self.operator {
+            Operator::Equal => self.has_prefix(version),
+            Operator::NotEqual => !self.has_prefix(version),
+            Operator::Less => version.cmp_value(&self.version) == Ordering::Less,
+            Operator::LessOrEqual => version.cmp_value(&self.version) != Ordering::Greater,
+            Operator::Greater => version.cmp_value(&self.version) == Ordering::Greater,
+            Operator::GreaterOrEqual => version.cmp_value(&self.version) != Ordering::Less,
+        }
+    }
+
+    /// Compares only the components present in the constraint version.
+    fn has_prefix(&self, version: &Version) -> bool {
+        let actual = version.components();
+        let expected = self.version.components();
+        expected
+            .iter()
+            .enumerate()
+            .all(|(i, c)| actual.get(i).copied().unwrap_or(0) == *c)
+            && (self.version.pre_release().is_none()
+                || self.version.pre_release() == version.pre_release())
+    }
+}
+
+/// A version comparison operator.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Operator {
+    /// `=`: all components of the constraint version are equal.
+    Equal,
+    /// `!=`: some components of the constraint version differ.
+    NotEqual,
+    /// `<`.
+    Less,
+    /// `<=`.
+    LessOrEqual,
+    /// `>`.
+    Greater,
+    /// `>=`.
+    GreaterOrEqual,
+}
+
+impl Display for Operator {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Operator::Equal => "=",
+            Operator::NotEqual => "!=",
+            Operator::Less => "<",
+            Operator::LessOrEqual => "<=",
+            Operator::Greater => ">",
+            Operator::GreaterOrEqual => ">=",
+        })
+    }
+}
+
+/// An error which can be returned when parsing a `Requirement`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequirementParseError {
+    position: usize,
+    message: String,
+}
+
+impl RequirementParseError {
+    fn new(position: usize, message: String) -> Self {
+        Self { position, message }
+    }
+
+    /// Returns the byte offset in the input where the error was detected.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl Display for RequirementParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} at position {}", self.message, self.position)
+    }
+}
+
+impl Error for RequirementParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Name(&'a str),
+    Version(&'a str),
+    Operator(Operator),
+    Range,
+    Comma,
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl Display for Token<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match *self {
+            Token::Name(name) => write!(f, "name '{}'", name),
+            Token::Version(version) => write!(f, "version '{}'", version),
+            Token::Operator(operator) => write!(f, "'{}'", operator),
+            Token::Range => f.write_str("'..'"),
+            Token::Comma => f.write_str("','"),
+            Token::And => f.write_str("'&&'"),
+            Token::Or => f.write_str("'||'"),
+            Token::Not => f.write_str("'!'"),
+            Token::Open => f.write_str("'('"),
+            Token::Close => f.write_str("')'"),
+        }
+    }
+}
+
+fn tokenize(s: &str) -> Result<Vec<(usize, Token<'_>)>, RequirementParseError> {
+    const SYMBOLS: [(&str, Token); 13] = [
+        ("&&", Token::And),
+        ("||", Token::Or),
+        ("..", Token::Range),
+        (">=", Token::Operator(Operator::GreaterOrEqual)),
+        ("<=", Token::Operator(Operator::LessOrEqual)),
+        ("!=", Token::Operator(Operator::NotEqual)),
+        (">", Token::Operator(Operator::Greater)),
+        ("<", Token::Operator(Operator::Less)),
+        ("=", Token::Operator(Operator::Equal)),
+        ("!", Token::Not),
+        ("(", Token::Open),
+        (")", Token::Close),
+        (",", Token::Comma),
+    ];
+
+    let mut tokens = Vec::new();
+    let mut position = 0;
+
+    while position < s.len() {
+        let rest = &s[position..];
+        let c = rest.chars().next().unwrap_or_default();
+
+        if c.is_whitespace() {
+            position += c.len_utf8();
+        } else if let Some((symbol, token)) = SYMBOLS.iter().find(|(sym, _)| rest.starts_with(sym))
+        {
+            tokens.push((position, *token));
+            position += symbol.len();
+        } else if c.is_ascii_digit() {
+            // Versions end before a range operator.
+            let len = rest
+                .char_indices()
+                .find(|&(i, c)| {
+                    !(c.is_ascii_alphanumeric() || "._~+-".contains(c))
+                        || rest[i..].starts_with("..")
+                })
+                .map_or(rest.len(), |(i, _)| i);
+            tokens.push((position, Token::Version(rest[..len].trim_end_matches('.'))));
+            position += len;
+        } else if c.is_ascii_alphabetic() {
+            let len = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || "_-:".contains(c)))
+                .unwrap_or(rest.len());
+            tokens.push((position, Token::Name(&rest[..len])));
+            position += len;
+        } else {
+            return Err(RequirementParseError::new(
+                position,
+                format!("unexpected character '{}'", c),
+            ));
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser<'a> {
+    tokens: &'a [(usize, Token<'a>)],
+    position: usize,
+    end: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<(usize, Token<'a>)> {
+        self.tokens.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<(usize, Token<'a>)> {
+        let token = self.peek();
+        self.position += 1;
+        token
+    }
+
+    fn eat(&mut self, expected: Token) -> bool {
+        if self.peek().map(|(_, t)| t) == Some(expected) {
+            self.position += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn error(&self, expected: &str) -> RequirementParseError {
+        match self.peek() {
+            Some((position, token)) => RequirementParseError::new(
+                position,
+                format!("expected {}, found {}", expected, token),
+            ),
+            None => RequirementParseError::new(self.end, format!("expected {}", expected)),
+        }
+    }
+
+    fn parse_any(&mut self) -> Result<Requirement, RequirementParseError> {
+        let mut requirements = vec![self.parse_all()?];
+        while self.eat(Token::Or) {
+            requirements.push(self.parse_all()?);
+        }
+        Ok(flatten(requirements, Requirement::Any))
+    }
+
+    fn parse_all(&mut self) -> Result<Requirement, RequirementParseError> {
+        let mut requirements = vec![self.parse_unary()?];
+        while self.eat(Token::And) {
+            requirements.push(self.parse_unary()?);
+        }
+        Ok(flatten(requirements, Requirement::All))
+    }
+
+    fn parse_unary(&mut self) -> Result<Requirement, RequirementParseError> {
+        match self.peek() {
+            Some((_, Token::Not)) => {
+                self.next();
+                Ok(Requirement::Not(Box::new(self.parse_unary()?)))
+            }
+            Some((_, Token::Open)) => {
+                self.next();
+                let requirement = self.parse_any()?;
+                if !self.eat(Token::Close) {
+                    return Err(self.error("')'"));
+                }
+                Ok(requirement)
+            }
+            Some((position, Token::Name(name))) => {
+                self.next();
+                self.parse_term(position, name)
+            }
+            _ => Err(self.error("operating system name, '!' or '('")),
+        }
+    }
+
+    fn parse_term(
+        &mut self,
+        position: usize,
+        name: &str,
+    ) -> Result<Requirement, RequirementParseError> {
+        if let Some(family) = name.strip_prefix("family:") {
+            return parse_family(family)
+                .map(Requirement::Family)
+                .ok_or_else(|| {
+                    RequirementParseError::new(position, format!("unknown family '{}'", family))
+                });
+        }
+
+        let os_type = name.parse::<Type>().map_err(|_| {
+            RequirementParseError::new(position, format!("unknown operating system '{}'", name))
+        })?;
+
+        let mut constraints = Vec::new();
+        if matches!(
+            self.peek(),
+            Some((_, Token::Operator(_))) | Some((_, Token::Version(_)))
+        ) {
+            loop {
+                self.parse_constraint(&mut constraints)?;
+                if !self.eat(Token::Comma) {
+                    break;
+                }
+            }
+        }
+
+        Ok(Requirement::Type(os_type, constraints))
+    }
+
+    fn parse_constraint(
+        &mut self,
+        constraints: &mut Vec<Constraint>,
+    ) -> Result<(), RequirementParseError> {
+        let operator = match self.peek() {
+            Some((_, Token::Operator(operator))) => {
+                self.next();
+                Some(operator)
+            }
+            _ => None,
+        };
+        let version = self.parse_version()?;
+
+        match operator {
+            Some(operator) => constraints.push(Constraint::new(operator, version)),
+            None if self.eat(Token::Range) => {
+                let end = self.parse_version()?;
+                constraints.push(Constraint::new(Operator::GreaterOrEqual, version));
+                constraints.push(Constraint::new(Operator::Less, end));
+            }
+            None => constraints.push(Constraint::new(Operator::Equal, version)),
+        }
+
+        Ok(())
+    }
+
+    fn parse_version(&mut self) -> Result<Version, RequirementParseError> {
+        match self.peek() {
+            Some((_, Token::Version(version))) => {
+                self.next();
+                Ok(Version::from_string_exact(version))
+            }
+            _ => Err(self.error("version")),
+        }
+    }
+}
+
+fn flatten(
+    mut requirements: Vec<Requirement>,
+    f: fn(Vec<Requirement>) -> Requirement,
+) -> Requirement {
+    if requirements.len() == 1 {
+        requirements.remove(0)
+    } else {
+        f(requirements)
+    }
+}
+
+/// Checks whether the detected type is the required one. Red Hat Enterprise Linux is detected from
+/// its release files as `Type::Redhat`, so it matches `Type::RedHatEnterprise` too.
+fn same_type(required: Type, detected: Type) -> bool {
+    required == detected || (required == Type::RedHatEnterprise && detected == Type::Redhat)
+}
+
+fn parse_family(name: &str) -> Option<Family> {
+    match name.to_lowercase().as_str() {
+        "debian" => Some(Family::Debian),
+        "redhat" | "rhel" | "fedora" => Some(Family::RedHat),
+        "suse" => Some(Family::SUSE),
+        "arch" => Some(Family::Arch),
+        "alpine" => Some(Family::Alpine),
+        "gentoo" => Some(Family::Gentoo),
+        "independent" => Some(Family::Independent),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn info(os_type: Type, version: &str) -> Info {
+        Info {
+            os_type,
+            version: Version::from_string(version),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let data = [
+            ("ubuntu", Requirement::Type(Type::Ubuntu, vec![])),
+            (
+                "rhel 8",
+                Requirement::Type(
+                    Type::RedHatEnterprise,
+                    vec![Constraint::new(
+                        Operator::Equal,
+                        Version::Extended(vec![8], None),
+                    )],
+                ),
+            ),
+            (
+                "ubuntu>=20.04",
+                Requirement::Type(
+                    Type::Ubuntu,
+                    vec![Constraint::new(
+                        Operator::GreaterOrEqual,
+                        Version::Extended(vec![20, 4], None),
+                    )],
+                ),
+            ),
+            (
+                "ubuntu 20.04..24.04",
+                Requirement::Type(
+                    Type::Ubuntu,
+                    vec![
+                        Constraint::new(
+                            Operator::GreaterOrEqual,
+                            Version::Extended(vec![20, 4], None),
+                        ),
+                        Constraint::new(Operator::Less, Version::Extended(vec![24, 4], None)),
+                    ],
+                ),
+            ),
+            (
+                "debian > 9, != 11",
+                Requirement::Type(
+                    Type::Debian,
+                    vec![
+                        Constraint::new(Operator::Greater, Version::Extended(vec![9], None)),
+                        Constraint::new(Operator::NotEqual, Version::Extended(vec![11], None)),
+                    ],
+                ),
+            ),
+            ("family:debian", Requirement::Family(Family::Debian)),
+            (
+                "!windows && (macos || family:arch)",
+                Requirement::All(vec![
+                    Requirement::Not(Box::new(Requirement::Type(Type::Windows, vec![]))),
+                    Requirement::Any(vec![
+                        Requirement::Type(Type::Macos, vec![]),
+                        Requirement::Family(Family::Arch),
+                    ]),
+                ]),
+            ),
+            (
+                "alpine || arch && manjaro",
+                Requirement::Any(vec![
+                    Requirement::Type(Type::Alpine, vec![]),
+                    Requirement::All(vec![
+                        Requirement::Type(Type::Arch, vec![]),
+                        Requirement::Type(Type::Manjaro, vec![]),
+                    ]),
+                ]),
+            ),
+        ];
+
+        for (s, expected) in &data {
+            assert_eq!(s.parse::<Requirement>().as_ref(), Ok(expected), "{}", s);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        let data = [
+            (
+                "",
+                "expected operating system name, '!' or '(' at position 0",
+            ),
+            ("beos", "unknown operating system 'beos' at position 0"),
+            ("family:plan9", "unknown family 'plan9' at position 0"),
+            ("ubuntu >=", "expected version at position 9"),
+            ("ubuntu >= 20.04,", "expected version at position 16"),
+            ("ubuntu 20.04..", "expected version at position 14"),
+            ("(ubuntu", "expected ')' at position 7"),
+            ("ubuntu debian", "unexpected name 'debian' at position 7"),
+            (
+                "ubuntu || ",
+                "expected operating system name, '!' or '(' at position 10",
+            ),
+            (
+                "ubuntu && >= 1",
+                "expected operating system name, '!' or '(', found '>=' at position 10",
+            ),
+            ("ubuntu $", "unexpected character '$' at position 7"),
+        ];
+
+        for (s, expected) in &data {
+            let error = s.parse::<Requirement>().unwrap_err();
+            assert_eq!(error.to_string(), *expected, "{}", s);
+        }
+    }
+
+    #[test]
+    fn matches() {
+        let data = [
+            ("ubuntu", info(Type::Ubuntu, "18.04"), true),
+            ("ubuntu", info(Type::Debian, "11"), false),
+            ("ubuntu >= 20.04", info(Type::Ubuntu, "22.04"), true),
+            ("ubuntu >= 20.04", info(Type::Ubuntu, "18.04"), false),
+            ("ubuntu >= 20.04", info(Type::Ubuntu, ""), false),
+            ("rhel 8", info(Type::RedHatEnterprise, "8.6"), true),
+            ("rhel 8", info(Type::RedHatEnterprise, "9.0"), false),
+            ("rhel >= 8", info(Type::Redhat, "8.2"), true),
+            ("rhel >= 9", info(Type::Redhat, "8.2"), false),
+            ("rhel != 8", info(Type::RedHatEnterprise, "9.0"), true),
+            ("ubuntu 20.04..24.04", info(Type::Ubuntu, "22.04"), true),
+            ("ubuntu 20.04..24.04", info(Type::Ubuntu, "24.04"), false),
+            ("ubuntu <= 20.04", info(Type::Ubuntu, "20.04"), true),
+            ("ubuntu > 20.04", info(Type::Ubuntu, "20.04.0"), false),
+            ("alpine < 3.16", info(Type::Alpine, "3.16.0_rc1"), true),
+            ("alpine < 3.16.0", info(Type::Alpine, "3.15.4"), true),
+            (
+                "ubuntu >= 20.04 || rhel >= 8",
+                info(Type::RedHatEnterprise, "8.2"),
+                true,
+            ),
+            (
+                "ubuntu >= 20.04 || rhel >= 8",
+                info(Type::CentOS, "8.2"),
+                false,
+            ),
+            ("family:redhat && !fedora", info(Type::CentOS, "7"), true),
+            ("family:redhat && !fedora", info(Type::Fedora, "35"), false),
+        ];
+
+        for (s, info, expected) in &data {
+            let requirement: Requirement = s.parse().unwrap();
+            assert_eq!(requirement.matches(info), *expected, "{} for {}", s, info);
+        }
+    }
+
+    #[test]
+    fn custom_version() {
+        let info = Info {
+            os_type: Type::Ubuntu,
+            version: Version::Custom("16.04".to_owned()),
+            ..Default::default()
+        };
+        assert!(info.satisfies(&"ubuntu 16.04".parse().unwrap()));
+        assert!(info.satisfies(&"ubuntu < 18.04".parse().unwrap()));
+    }
+
+    #[test]
+    fn custom_type() {
+        use crate::{CustomType, Distribution};
+
+        const CUSTOM: CustomType =
+            CustomType::new("requirement-test", "Requirement Test", Family::RedHat);
+        Distribution::new(Type::Other(CUSTOM)).register();
+
+        let requirement: Requirement = "requirement-test >= 2 || ubuntu".parse().unwrap();
+        assert!(info(Type::Other(CUSTOM), "2.1").satisfies(&requirement));
+        assert!(!info(Type::Other(CUSTOM), "1.9").satisfies(&requirement));
+        assert!("unregistered-test".parse::<Requirement>().is_err());
+    }
+}
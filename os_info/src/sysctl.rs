@@ -0,0 +1,96 @@
+// spell-checker:ignore sysctl, sysctlbyname, oldlen, osproductversion
+
+#![allow(unsafe_code)]
+
+#[cfg(not(target_os = "openbsd"))]
+use std::ffi::CString;
+use std::{io, os::raw::c_void, ptr};
+
+use log::error;
+
+use crate::DetectionError;
+
+/// Name of the `sysctl` value holding the processor architecture of the userland.
+#[cfg(any(target_os = "dragonfly", target_os = "freebsd", target_os = "netbsd"))]
+pub const MACHINE_ARCH: &str = "hw.machine_arch";
+#[cfg(any(target_os = "macos", target_os = "openbsd"))]
+pub const MACHINE_ARCH: &str = "hw.machine";
+
+/// Management information base (MIB) numbers of the values read on OpenBSD, which doesn't provide
+/// `sysctlbyname`.
+#[cfg(target_os = "openbsd")]
+const MIBS: [(&str, [libc::c_int; 2]); 1] = [("hw.machine", [libc::CTL_HW, 1])];
+
+/// Reads a string value using the `sysctl` system call. Unlike the `sysctl` command this doesn't
+/// spawn a process.
+pub fn sysctl(name: &str) -> Result<String, DetectionError> {
+    let failed = |e: io::Error| {
+        error!("'sysctl {}' call failed: {:?}", name, e);
+        DetectionError::SystemCallFailed {
+            name: format!("sysctl {}", name),
+            message: e.to_string(),
+        }
+    };
+
+    // The first call determines the size of the value, the second one reads it.
+    let mut len = 0;
+    call(name, ptr::null_mut(), &mut len).map_err(failed)?;
+    let mut value = vec![0_u8; len];
+    call(name, value.as_mut_ptr() as *mut c_void, &mut len).map_err(failed)?;
+
+    value.truncate(len);
+    while value.last() == Some(&0) {
+        value.pop();
+    }
+    Ok(String::from_utf8_lossy(&value).into_owned())
+}
+
+#[cfg(not(target_os = "openbsd"))]
+fn call(name: &str, value: *mut c_void, len: &mut libc::size_t) -> io::Result<()> {
+    let name = CString::new(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+    let result = unsafe { libc::sysctlbyname(name.as_ptr(), value, len, ptr::null_mut(), 0) };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(target_os = "openbsd")]
+fn call(name: &str, value: *mut c_void, len: &mut libc::size_t) -> io::Result<()> {
+    let mib = MIBS
+        .iter()
+        .find(|(n, _)| *n == name)
+        .map(|(_, mib)| mib)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "unsupported name"))?;
+    let result = unsafe {
+        libc::sysctl(
+            mib.as_ptr(),
+            mib.len() as libc::c_uint,
+            value,
+            len,
+            ptr::null_mut(),
+            0,
+        )
+    };
+    if result < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn machine() {
+        let machine = sysctl("hw.machine").expect("sysctl failed");
+        assert_eq!(machine, crate::uname::uname().unwrap().machine());
+    }
+
+    #[test]
+    fn unknown_name() {
+        assert!(sysctl("os_info.missing").is_err());
+    }
+}
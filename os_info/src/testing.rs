@@ -0,0 +1,301 @@
+//! Overriding the detected operating system, so that code depending on it can be tested on any
+//! host.
+//!
+//! `os_info::get` and the other detection functions return the overriding information instead of
+//! detecting the current system if:
+//!
+//! - an override set with `override_with` is active on the calling thread;
+//! - otherwise, the `OS_INFO_OVERRIDE` environment variable is set to a value such as
+//!   `ubuntu:22.04:x86_64` (see `parse_override` for the format). The variable is only read when
+//!   the `testing` feature is enabled, so it can't change the detection in production builds
+//!   unless that feature is enabled, for example as a dev-dependency feature.
+//!
+//! Detection of another root directory (`Detector::root`) is never overridden. The provenance of
+//! overriding information consists of a single `Source::Override` record.
+//!
+//! # Examples
+//!
+//! ```
+//! use os_info::{testing, Info, Type, Version};
+//!
+//! let info = Info::builder(Type::Macos)
+//!     .version(Version::Semantic(14, 2, 1))
+//!     .build()
+//!     .unwrap();
+//! {
+//!     let _guard = testing::override_with(info);
+//!     assert_eq!(Type::Macos, os_info::get().os_type());
+//! }
+//! // The detection isn't overridden anymore.
+//! ```
+
+#[cfg(feature = "testing")]
+use std::env;
+use std::{
+    cell::RefCell,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    marker::PhantomData,
+};
+
+use log::trace;
+#[cfg(feature = "testing")]
+use log::warn;
+
+use super::{lookup_codename, Architecture, Info, Provenance, Source, SourceStatus, Type, Version};
+
+/// Name of the environment variable that overrides the detected operating system. Only available
+/// with the `testing` feature.
+#[cfg(feature = "testing")]
+pub const OVERRIDE_VARIABLE: &str = "OS_INFO_OVERRIDE";
+
+thread_local! {
+    /// The information set with `override_with` on this thread.
+    static OVERRIDE: RefCell<Option<Info>> = const { RefCell::new(None) };
+}
+
+/// Makes the detection on the calling thread return the given information until the returned
+/// guard is dropped. Overrides can be nested: dropping a guard restores the previous override.
+///
+/// The override is thread-local, so tests running in parallel don't affect each other. It's also
+/// applied to `Detector::detect_async`, which performs the detection on another thread.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::{testing, Info, Type};
+///
+/// let _guard = testing::override_with(Info::with_type(Type::Ubuntu));
+/// assert_eq!(Type::Ubuntu, os_info::get().os_type());
+/// ```
+#[must_use = "the override is removed when the guard is dropped"]
+pub fn override_with(info: Info) -> OverrideGuard {
+    trace!("Overriding the detection with {:?}", info);
+    let previous = OVERRIDE.with(|current| current.borrow_mut().replace(info));
+    OverrideGuard {
+        previous,
+        _not_send: PhantomData,
+    }
+}
+
+/// Restores the previous override of the thread when dropped. Returned by `override_with`.
+#[derive(Debug)]
+pub struct OverrideGuard {
+    previous: Option<Info>,
+    // The guard must be dropped on the thread that created it.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl Drop for OverrideGuard {
+    fn drop(&mut self) {
+        let previous = self.previous.take();
+        OVERRIDE.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Parses an override in the `type[:version[:architecture]]` format used by the
+/// `OS_INFO_OVERRIDE` environment variable (see the `testing` feature).
+///
+/// The type is an identifier or an alias accepted by `Type::from_str`, the version is parsed with
+/// `Version::from_string`, and the architecture is a machine name accepted by
+/// `Architecture::from_machine`, which also determines the bitness. The codename is looked up for
+/// the version if it's known.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::{testing, Architecture, Bitness, Type};
+///
+/// let info = testing::parse_override("ubuntu:22.04:x86_64").unwrap();
+/// assert_eq!(Type::Ubuntu, info.os_type());
+/// assert_eq!(Some(22), info.version().major());
+/// assert_eq!(Architecture::X86_64, info.architecture());
+/// assert_eq!(Bitness::X64, info.bitness());
+/// assert_eq!(Some("jammy"), info.codename());
+///
+/// assert!(testing::parse_override("ubuntu:22.04:vax").is_err());
+/// ```
+pub fn parse_override(value: &str) -> Result<Info, OverrideParseError> {
+    let mut parts = value.trim().splitn(3, ':');
+    let error = |message: String| OverrideParseError {
+        value: value.to_owned(),
+        message,
+    };
+
+    let os_type = parts
+        .next()
+        .unwrap_or_default()
+        .parse::<Type>()
+        .map_err(|e| error(e.to_string()))?;
+    let version = Version::from_string(parts.next().unwrap_or_default().trim());
+    let architecture = match parts.next().map(str::trim) {
+        None | Some("") => Architecture::Unknown,
+        Some(machine) => match Architecture::from_machine(machine) {
+            Architecture::Unknown => {
+                return Err(error(format!("unknown architecture '{}'", machine)))
+            }
+            architecture => architecture,
+        },
+    };
+
+    let mut info = Info::with_type(os_type);
+    info.codename = lookup_codename(os_type, &version).map(str::to_owned);
+    info.version = version;
+    info.architecture = architecture;
+    info.bitness = architecture.bitness();
+    Ok(info)
+}
+
+/// An error which can be returned when parsing an override.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverrideParseError {
+    value: String,
+    message: String,
+}
+
+impl Display for OverrideParseError {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "invalid override '{}': {}", self.value, self.message)
+    }
+}
+
+impl Error for OverrideParseError {}
+
+/// Returns the override set on the calling thread.
+#[cfg(feature = "async")]
+pub(crate) fn scoped() -> Option<Info> {
+    OVERRIDE.with(|current| current.borrow().clone())
+}
+
+/// Returns the information overriding the detection, if any.
+pub(crate) fn overridden() -> Option<Info> {
+    let (mut info, location) = match OVERRIDE.with(|current| current.borrow().clone()) {
+        Some(info) => (info, "os_info::testing::override_with".to_owned()),
+        None => from_environment()?,
+    };
+
+    let mut provenance = Provenance::default();
+    provenance.record(Source::Override, location, SourceStatus::Parsed);
+    provenance.select(&info);
+    info.provenance = provenance;
+    Some(info)
+}
+
+/// Parses the override set with the `OS_INFO_OVERRIDE` environment variable.
+#[cfg(feature = "testing")]
+fn from_environment() -> Option<(Info, String)> {
+    let value = env::var(OVERRIDE_VARIABLE).ok()?;
+    match parse_override(&value) {
+        Ok(info) => Some((info, format!("{}={}", OVERRIDE_VARIABLE, value))),
+        Err(e) => {
+            warn!("Ignoring {}: {}", OVERRIDE_VARIABLE, e);
+            None
+        }
+    }
+}
+
+#[cfg(not(feature = "testing"))]
+fn from_environment() -> Option<(Info, String)> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Bitness, Confidence};
+    use pretty_assertions::assert_eq;
+
+    // The environment is shared by all tests, so only the thread-local override is exercised.
+
+    #[test]
+    fn parse() {
+        let data = [
+            (
+                "ubuntu:22.04:x86_64",
+                Type::Ubuntu,
+                Version::Semantic(22, 4, 0),
+                Architecture::X86_64,
+            ),
+            (
+                "rhel:8.9",
+                Type::RedHatEnterprise,
+                Version::Semantic(8, 9, 0),
+                Architecture::Unknown,
+            ),
+            (
+                "macos::arm64",
+                Type::Macos,
+                Version::Unknown,
+                Architecture::Aarch64,
+            ),
+            (
+                "windows",
+                Type::Windows,
+                Version::Unknown,
+                Architecture::Unknown,
+            ),
+        ];
+
+        for (value, os_type, version, architecture) in &data {
+            let info = parse_override(value).unwrap();
+            assert_eq!(info.os_type(), *os_type, "{}", value);
+            assert_eq!(info.version(), version, "{}", value);
+            assert_eq!(info.architecture(), *architecture, "{}", value);
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        let data = [
+            ("", "invalid override '': unknown operating system type ''"),
+            (
+                "nope:1",
+                "invalid override 'nope:1': unknown operating system type 'nope'",
+            ),
+            (
+                "debian:12:vax",
+                "invalid override 'debian:12:vax': unknown architecture 'vax'",
+            ),
+        ];
+
+        for (value, expected) in &data {
+            assert_eq!(parse_override(value).unwrap_err().to_string(), *expected);
+        }
+    }
+
+    #[test]
+    fn scoped_override() {
+        let debian = Info {
+            version: Version::Semantic(12, 0, 0),
+            bitness: Bitness::X64,
+            ..Info::with_type(Type::Debian)
+        };
+        {
+            let _outer = override_with(debian.clone());
+            {
+                let _inner = override_with(Info::with_type(Type::Macos));
+                assert_eq!(crate::get().os_type(), Type::Macos);
+            }
+
+            let info = crate::try_get().unwrap();
+            assert_eq!(info.version(), debian.version());
+            assert_eq!(info.provenance().confidence(), Confidence::High);
+            assert_eq!(
+                info.provenance().selected().map(|r| r.source()),
+                Some(Source::Override)
+            );
+            assert_eq!(*crate::get_cached(), info);
+        }
+        assert_eq!(OVERRIDE.with(|current| current.borrow().clone()), None);
+    }
+
+    #[test]
+    fn other_threads() {
+        let _guard = override_with(Info::with_type(Type::Macos));
+        let os_type = std::thread::spawn(|| crate::get().os_type())
+            .join()
+            .unwrap();
+        let detected = crate::imp::current_platform(&crate::Detector::new());
+        assert_eq!(os_type, detected.os_type());
+    }
+}
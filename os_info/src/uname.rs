@@ -1,32 +1,66 @@
-use std::process::Command;
+// spell-checker:ignore sysname, utsname, nodename
+
+#![allow(unsafe_code)]
+
+use std::{mem, os::raw::c_char};
 
 use log::error;
 
-pub fn uname() -> Option<String> {
-    Command::new("uname")
-        .arg("-r")
-        .output()
-        .map_err(|e| {
-            error!("Failed to invoke 'uname': {:?}", e);
-        })
-        .ok()
-        .and_then(|out| {
-            if out.status.success() {
-                Some(String::from_utf8_lossy(&out.stdout).trim_end().to_owned())
-            } else {
-                log::error!("'uname' invocation error: {:?}", out);
-                None
-            }
-        })
+use crate::{DetectionError, KernelInfo};
+
+/// Calls the `uname` system call. Unlike the `uname` command this doesn't spawn a process.
+pub fn uname() -> Result<KernelInfo, DetectionError> {
+    let mut name: libc::utsname = unsafe { mem::zeroed() };
+    if unsafe { libc::uname(&mut name) } < 0 {
+        let e = std::io::Error::last_os_error();
+        error!("'uname' call failed: {:?}", e);
+        return Err(DetectionError::SystemCallFailed {
+            name: "uname".to_owned(),
+            message: e.to_string(),
+        });
+    }
+
+    Ok(KernelInfo {
+        sysname: to_string(&name.sysname),
+        release: to_string(&name.release),
+        version: to_string(&name.version),
+        machine: to_string(&name.machine),
+    })
+}
+
+fn to_string(field: &[c_char]) -> String {
+    // The fields are NUL-terminated, but don't rely on it.
+    let bytes: Vec<u8> = field
+        .iter()
+        .take_while(|&&c| c != 0)
+        .map(|&c| c as u8)
+        .collect();
+    String::from_utf8_lossy(&bytes).into_owned()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn uname_nonempty() {
         let val = uname().expect("uname failed");
-        assert!(!val.is_empty());
+        assert!(!val.sysname().is_empty());
+        assert!(!val.release().is_empty());
+        assert!(!val.machine().is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn linux_sysname() {
+        assert_eq!(uname().unwrap().sysname(), "Linux");
+    }
+
+    #[test]
+    fn field_to_string() {
+        let field = [b'a' as c_char, b'b' as c_char, 0, b'c' as c_char];
+        assert_eq!(to_string(&field), "ab");
+        assert_eq!(to_string(&[b'x' as c_char; 3]), "x".repeat(3));
     }
 }
@@ -1,9 +1,9 @@
 use log::trace;
 
-use crate::{Info, Type};
+use crate::{Detector, Info, Type};
 
-pub fn current_platform() -> Info {
-    trace!("unknown::current_platform is called");
+pub fn current_platform(detector: &Detector) -> Info {
+    trace!("unknown::current_platform({:?}) is called", detector);
     Info::unknown()
 }
 
@@ -14,7 +14,7 @@ mod tests {
 
     #[test]
     fn os_type() {
-        let version = current_platform();
+        let version = current_platform(&Detector::default());
         assert_eq!(Type::Unknown, version.os_type());
     }
 }
@@ -0,0 +1,150 @@
+use super::{lookup_codename, lookup_release, Architecture, Bitness, Info, Type};
+
+/// Fields of the `os-release` file that name the release a derivative is based on, together with
+/// the type of the base distribution. Derivatives that follow Debian releases closely, such as
+/// Raspberry Pi OS, only use the codename of the Debian release as their own.
+const CODENAME_FIELDS: [(&str, Type); 3] = [
+    ("UBUNTU_CODENAME", Type::Ubuntu),
+    ("DEBIAN_CODENAME", Type::Debian),
+    ("VERSION_CODENAME", Type::Debian),
+];
+
+/// Returns the base distribution of the given derivative. See `Info::upstream` for details.
+pub(crate) fn find(info: &Info) -> Option<Info> {
+    let mut upstream = match &info.upstream {
+        Some(upstream) => Info::clone(upstream),
+        None => from_os_release(info)?,
+    };
+    if upstream.os_type == info.os_type {
+        return None;
+    }
+
+    if upstream.codename.is_none() {
+        upstream.codename = lookup_codename(upstream.os_type, &upstream.version).map(str::to_owned);
+    }
+    // The base distribution runs on the same machine.
+    if upstream.bitness == Bitness::Unknown {
+        upstream.bitness = info.bitness;
+    }
+    if upstream.architecture == Architecture::Unknown {
+        upstream.architecture = info.architecture;
+    }
+    if upstream.kernel.is_none() {
+        upstream.kernel = info.kernel.clone();
+    }
+    Some(upstream)
+}
+
+fn from_os_release(info: &Info) -> Option<Info> {
+    let os_release = info.os_release.as_ref()?;
+    let (os_type, version) = CODENAME_FIELDS.iter().find_map(|(field, base)| {
+        lookup_release(os_release.get(field)?)
+            .filter(|(os_type, _)| os_type == base && *os_type != info.os_type)
+    })?;
+
+    let mut upstream = Info::with_type(os_type);
+    upstream.version = version;
+    Some(upstream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{OsRelease, Version};
+    use pretty_assertions::assert_eq;
+
+    fn derivative(os_type: Type, os_release: &str) -> Info {
+        Info {
+            bitness: Bitness::X64,
+            os_release: Some(OsRelease::parse(os_release)),
+            ..Info::with_type(os_type)
+        }
+    }
+
+    #[test]
+    fn os_release() {
+        let data = [
+            (
+                derivative(
+                    Type::Mint,
+                    "ID=linuxmint\nVERSION_CODENAME=ulyana\nUBUNTU_CODENAME=focal",
+                ),
+                Some((Type::Ubuntu, Version::Semantic(20, 4, 0), "focal")),
+            ),
+            (
+                derivative(
+                    Type::Pop,
+                    "ID=pop\nVERSION_CODENAME=jammy\nUBUNTU_CODENAME=jammy",
+                ),
+                Some((Type::Ubuntu, Version::Semantic(22, 4, 0), "jammy")),
+            ),
+            (
+                derivative(
+                    Type::Linux,
+                    "ID=zorin\nID_LIKE=\"ubuntu debian\"\nUBUNTU_CODENAME=noble",
+                ),
+                Some((Type::Ubuntu, Version::Semantic(24, 4, 0), "noble")),
+            ),
+            (
+                derivative(
+                    Type::Mint,
+                    "ID=linuxmint\nID_LIKE=debian\nVERSION_CODENAME=faye\nDEBIAN_CODENAME=bookworm",
+                ),
+                Some((Type::Debian, Version::Semantic(12, 0, 0), "bookworm")),
+            ),
+            (
+                derivative(
+                    Type::Raspbian,
+                    "ID=raspbian\nID_LIKE=debian\nVERSION_ID=\"11\"\nVERSION_CODENAME=bullseye",
+                ),
+                Some((Type::Debian, Version::Semantic(11, 0, 0), "bullseye")),
+            ),
+            (
+                derivative(
+                    Type::Ubuntu,
+                    "ID=ubuntu\nVERSION_CODENAME=jammy\nUBUNTU_CODENAME=jammy",
+                ),
+                None,
+            ),
+            (
+                derivative(Type::Debian, "ID=debian\nVERSION_CODENAME=bookworm"),
+                None,
+            ),
+            (derivative(Type::Fedora, "ID=fedora"), None),
+            (Info::with_type(Type::Mint), None),
+        ];
+
+        for (info, expected) in &data {
+            let upstream = find(info);
+            assert_eq!(
+                upstream.as_ref().map(|u| (
+                    u.os_type(),
+                    u.version().clone(),
+                    u.codename().unwrap()
+                )),
+                *expected,
+                "{:?}",
+                info.os_release()
+            );
+            if let Some(upstream) = upstream {
+                assert_eq!(upstream.bitness(), Bitness::X64);
+                assert_eq!(upstream.os_release(), None);
+            }
+        }
+    }
+
+    #[test]
+    fn stored() {
+        let info = Info {
+            upstream: Some(Box::new(Info {
+                version: Version::Custom("20.04".to_owned()),
+                ..Info::with_type(Type::Ubuntu)
+            })),
+            ..derivative(Type::Mint, "ID=linuxmint\nUBUNTU_CODENAME=jammy")
+        };
+        let upstream = find(&info).unwrap();
+        assert_eq!(upstream.version(), &Version::Custom("20.04".to_owned()));
+        assert_eq!(upstream.codename(), Some("focal"));
+        assert_eq!(upstream.bitness(), Bitness::X64);
+    }
+}
@@ -1,13 +1,38 @@
-use std::fmt::{self, Display, Formatter};
+use std::{
+    borrow::Cow,
+    cmp::Ordering,
+    fmt::{self, Display, Formatter},
+};
 
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
 /// Operating system version.
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+///
+/// Versions are ordered by their value rather than by their representation: numeric components
+/// are compared one by one (missing components are treated as zeros), a version with a
+/// pre-release suffix precedes the same version without it, and `Custom` versions that look like
+/// numbers are compared as numbers. Release and patch level suffixes of the BSDs (`-RELEASE`,
+/// `-RELEASE-p3` or `-p3`) aren't pre-releases and follow the version without a suffix. `Unknown`
+/// precedes all other versions, and `Rolling` follows them.
+///
+/// # Examples
+///
+/// ```
+/// use os_info::Version;
+///
+/// assert!(Version::from_string("3.16.0_rc1") < Version::from_string("3.16"));
+/// assert!(Version::from_string("10.0.19044.2006") < Version::from_string("10.0.19045"));
+/// assert!(Version::Custom("9.10".to_owned()) < Version::Semantic(10, 0, 0));
+/// assert!(Version::from_string("13.1") < Version::from_string("13.1-RELEASE-p3"));
+/// ```
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "schemars", derive(schemars::JsonSchema))]
+#[non_exhaustive]
 pub enum Version {
     /// Unknown version.
+    #[default]
     Unknown,
     /// Semantic version (major.minor.patch).
     Semantic(u64, u64, u64),
@@ -15,13 +40,18 @@ pub enum Version {
     Rolling(Option<String>),
     /// Custom version format.
     Custom(String),
+    /// Version with an arbitrary number of numeric components and an optional suffix, for example
+    /// `10.0.19045.3570` or `3.16.0_rc1`. The suffix is stored as is, including the separator.
+    Extended(Vec<u64>, Option<String>),
 }
 
 impl Version {
     /// Constructs `VersionType` from the given string.
     ///
     /// Returns `VersionType::Unknown` if the string is empty. If it can be parsed as a semantic
-    /// version, then `VersionType::Semantic`, otherwise `VersionType::Custom`.
+    /// version, then `VersionType::Semantic`. Versions with more than three numeric components or
+    /// with a suffix are parsed as `VersionType::Extended`, otherwise `VersionType::Custom` is
+    /// returned.
     ///
     /// # Examples
     ///
@@ -33,21 +63,170 @@ impl Version {
     ///
     /// let v = Version::from_string("1.2.3");
     /// assert_eq!(Version::Semantic(1, 2, 3), v);
+    ///
+    /// let v = Version::from_string("1.2.3.4-beta");
+    /// assert_eq!(Version::Extended(vec![1, 2, 3, 4], Some("-beta".to_owned())), v);
     /// ```
     pub fn from_string<S: Into<String> + AsRef<str>>(s: S) -> Self {
         if s.as_ref().is_empty() {
-            Self::Unknown
-        } else if let Some((major, minor, patch)) = parse_version(s.as_ref()) {
-            Self::Semantic(major, minor, patch)
-        } else {
-            Self::Custom(s.into())
+            return Self::Unknown;
+        }
+
+        match parse_version(s.as_ref()) {
+            Some((components, None)) if components.len() <= 3 => Self::Semantic(
+                components[0],
+                components.get(1).copied().unwrap_or(0),
+                components.get(2).copied().unwrap_or(0),
+            ),
+            Some((components, suffix)) => Self::Extended(components, suffix.map(str::to_owned)),
+            None => Self::Custom(s.into()),
+        }
+    }
+
+    /// Parses the string like `from_string`, but keeps the exact number of numeric components, so
+    /// `8` and `8.0.0` remain distinguishable.
+    pub(crate) fn from_string_exact(s: &str) -> Self {
+        match parse_version(s) {
+            Some((components, suffix)) => Self::Extended(components, suffix.map(str::to_owned)),
+            None => Self::from_string(s),
+        }
+    }
+
+    /// Returns numeric components of the version, or an empty vector if the version isn't
+    /// numeric.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Version;
+    ///
+    /// assert_eq!(vec![1, 2, 3], Version::Semantic(1, 2, 3).components());
+    /// assert_eq!(vec![21, 5], Version::from_string("21.05pre275822").components());
+    /// assert_eq!(vec![16, 4], Version::Custom("16.04".to_owned()).components());
+    /// assert!(Version::Rolling(None).components().is_empty());
+    /// ```
+    pub fn components(&self) -> Vec<u64> {
+        self.numeric()
+            .map(|(components, _)| components.into_owned())
+            .unwrap_or_default()
+    }
+
+    /// Returns the major version number.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Version;
+    ///
+    /// assert_eq!(Some(10), Version::from_string("10.0.19045.3570").major());
+    /// assert_eq!(None, Version::Unknown.major());
+    /// ```
+    pub fn major(&self) -> Option<u64> {
+        self.component(0)
+    }
+
+    /// Returns the minor version number. Missing components of a numeric version are zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Version;
+    ///
+    /// assert_eq!(Some(4), Version::Custom("20.04".to_owned()).minor());
+    /// assert_eq!(Some(0), Version::from_string("11").minor());
+    /// assert_eq!(None, Version::Custom("XX".to_owned()).minor());
+    /// ```
+    pub fn minor(&self) -> Option<u64> {
+        self.component(1)
+    }
+
+    /// Returns the patch version number. Missing components of a numeric version are zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Version;
+    ///
+    /// assert_eq!(Some(2009), Version::from_string("7.9.2009").patch());
+    /// assert_eq!(Some(0), Version::from_string("3.16_rc1").patch());
+    /// ```
+    pub fn patch(&self) -> Option<u64> {
+        self.component(2)
+    }
+
+    /// Returns the pre-release or build suffix of the version without the leading separator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use os_info::Version;
+    ///
+    /// assert_eq!(Some("rc1"), Version::from_string("3.16.0_rc1").pre_release());
+    /// assert_eq!(None, Version::from_string("3.16.0").pre_release());
+    /// ```
+    pub fn pre_release(&self) -> Option<&str> {
+        self.numeric()
+            .and_then(|(_, suffix)| suffix)
+            .map(trim_separator)
+    }
+
+    fn component(&self, index: usize) -> Option<u64> {
+        self.numeric()
+            .map(|(components, _)| components.get(index).copied().unwrap_or(0))
+    }
+
+    /// Returns numeric components and the suffix if the version is numeric.
+    fn numeric(&self) -> Option<(Cow<'_, [u64]>, Option<&str>)> {
+        match self {
+            Self::Semantic(major, minor, patch) => {
+                Some((Cow::Owned(vec![*major, *minor, *patch]), None))
+            }
+            Self::Extended(components, suffix) if !components.is_empty() => {
+                Some((Cow::Borrowed(components.as_slice()), suffix.as_deref()))
+            }
+            Self::Custom(version) => {
+                parse_version(version).map(|(components, suffix)| (Cow::Owned(components), suffix))
+            }
+            _ => None,
+        }
+    }
+
+    /// Compares versions by value only, so different representations of the same version are
+    /// equal.
+    pub(crate) fn cmp_value(&self, other: &Self) -> Ordering {
+        match (self.numeric(), other.numeric()) {
+            (Some((a, a_suffix)), Some((b, b_suffix))) => {
+                compare_components(&a, &b).then_with(|| compare_suffixes(a_suffix, b_suffix))
+            }
+            _ => self
+                .rank()
+                .cmp(&other.rank())
+                .then_with(|| match (self, other) {
+                    (Self::Custom(a), Self::Custom(b)) => a.cmp(b),
+                    (Self::Rolling(a), Self::Rolling(b)) => a.cmp(b),
+                    _ => Ordering::Equal,
+                }),
         }
     }
-}
 
-impl Default for Version {
-    fn default() -> Self {
-        Version::Unknown
+    /// Rank of the version kind in the ordering: unknown, numeric, other custom, rolling.
+    fn rank(&self) -> u8 {
+        match self {
+            Self::Unknown => 0,
+            Self::Rolling(_) => 3,
+            _ if self.numeric().is_some() => 1,
+            _ => 2,
+        }
+    }
+
+    fn variant_index(&self) -> u8 {
+        match self {
+            Self::Unknown => 0,
+            Self::Semantic(..) => 1,
+            Self::Rolling(_) => 2,
+            Self::Custom(_) => 3,
+            Self::Extended(..) => 4,
+        }
     }
 }
 
@@ -64,22 +243,152 @@ impl Display for Version {
                 write!(f, "Rolling Release{}", date)
             }
             Self::Custom(ref version) => write!(f, "{}", version),
+            Self::Extended(ref components, ref suffix) => {
+                for (i, component) in components.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(".")?;
+                    }
+                    write!(f, "{}", component)?;
+                }
+                f.write_str(suffix.as_deref().unwrap_or(""))
+            }
         }
     }
 }
 
-fn parse_version(s: &str) -> Option<(u64, u64, u64)> {
-    let mut iter = s.trim().split_terminator('.').fuse();
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-    let major = iter.next().and_then(|s| s.parse().ok())?;
-    let minor = iter.next().unwrap_or("0").parse().ok()?;
-    let patch = iter.next().unwrap_or("0").parse().ok()?;
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Different representations of the same value (for example `1.2` and `1.2.0`) still need
+        // a stable order to stay consistent with `Eq`.
+        self.cmp_value(other)
+            .then_with(|| self.variant_index().cmp(&other.variant_index()))
+            .then_with(|| match (self, other) {
+                (Self::Extended(a, a_suffix), Self::Extended(b, b_suffix)) => {
+                    (a, a_suffix).cmp(&(b, b_suffix))
+                }
+                (Self::Custom(a), Self::Custom(b)) => a.cmp(b),
+                _ => Ordering::Equal,
+            })
+    }
+}
 
-    if iter.next().is_some() {
+/// Splits the string into leading numeric components separated by dots and the remaining suffix.
+/// Returns `None` if the string doesn't start with a number or if the suffix contains whitespace.
+fn parse_version(s: &str) -> Option<(Vec<u64>, Option<&str>)> {
+    let s = s.trim();
+    let mut components = Vec::new();
+    let mut rest = s;
+
+    loop {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if digits == 0 {
+            break;
+        }
+        components.push(rest[..digits].parse().ok()?);
+        rest = &rest[digits..];
+
+        match rest.strip_prefix('.') {
+            Some(r) if r.starts_with(|c: char| c.is_ascii_digit()) => rest = r,
+            Some(r) if r.is_empty() => rest = r,
+            _ => break,
+        }
+    }
+
+    if components.is_empty() || rest.contains(char::is_whitespace) || rest.starts_with('.') {
         return None;
     }
 
-    Some((major, minor, patch))
+    Some((components, Some(rest).filter(|r| !r.is_empty())))
+}
+
+fn trim_separator(suffix: &str) -> &str {
+    suffix.trim_start_matches(['-', '_', '~', '+'])
+}
+
+fn compare_components(a: &[u64], b: &[u64]) -> Ordering {
+    (0..a.len().max(b.len()))
+        .map(|i| {
+            let a = a.get(i).copied().unwrap_or(0);
+            let b = b.get(i).copied().unwrap_or(0);
+            a.cmp(&b)
+        })
+        .find(|&o| o != Ordering::Equal)
+        .unwrap_or(Ordering::Equal)
+}
+
+/// Compares suffixes so that a pre-release precedes the release and a release or patch level
+/// suffix follows it. Numbers in suffixes are compared by value (`rc2` < `rc10`).
+fn compare_suffixes(a: Option<&str>, b: Option<&str>) -> Ordering {
+    let (a, b) = (a.map(trim_separator), b.map(trim_separator));
+    let rank = |suffix: Option<&str>| match suffix {
+        None => 1,
+        Some(suffix) if is_release(suffix) => 2,
+        Some(_) => 0,
+    };
+    rank(a).cmp(&rank(b)).then_with(|| match (a, b) {
+        (Some(a), Some(b)) => natural_cmp(a, b),
+        _ => Ordering::Equal,
+    })
+}
+
+/// Checks whether the suffix marks a release or a patch level rather than a pre-release, as in
+/// `13.1-RELEASE-p3` or `7.4-p2`.
+fn is_release(suffix: &str) -> bool {
+    let patch_level = |s: &str| {
+        s.strip_prefix('p')
+            .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+    };
+    match suffix.get(..7) {
+        Some(release) if release.eq_ignore_ascii_case("release") => {
+            let rest = &suffix[7..];
+            rest.is_empty() || rest.strip_prefix('-').is_some_and(patch_level)
+        }
+        _ => patch_level(suffix),
+    }
+}
+
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        match (a.chars().next(), b.chars().next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let (x, rest_a) = split_number(a);
+                let (y, rest_b) = split_number(b);
+                let x = x.trim_start_matches('0');
+                let y = y.trim_start_matches('0');
+                match x.len().cmp(&y.len()).then_with(|| x.cmp(y)) {
+                    Ordering::Equal => {
+                        a = rest_a;
+                        b = rest_b;
+                    }
+                    o => return o,
+                }
+            }
+            (Some(x), Some(y)) => match x.cmp(&y) {
+                Ordering::Equal => {
+                    a = &a[x.len_utf8()..];
+                    b = &b[y.len_utf8()..];
+                }
+                o => return o,
+            },
+        }
+    }
+}
+
+fn split_number(s: &str) -> (&str, &str) {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    s.split_at(end)
 }
 
 #[cfg(test)]
@@ -92,22 +401,34 @@ mod tests {
         let data = [
             ("", None),
             ("version", None),
-            ("1", Some((1, 0, 0))),
-            ("1.", Some((1, 0, 0))),
-            ("1.2", Some((1, 2, 0))),
-            ("1.2.", Some((1, 2, 0))),
-            ("1.2.3", Some((1, 2, 3))),
-            ("1.2.3.", Some((1, 2, 3))),
-            ("1.2.3.  ", Some((1, 2, 3))),
-            ("   1.2.3.", Some((1, 2, 3))),
-            ("   1.2.3.  ", Some((1, 2, 3))),
-            ("1.2.3.4", None),
-            ("1.2.3.4.5.6.7.8.9", None),
+            ("1", Some((vec![1], None))),
+            ("1.", Some((vec![1], None))),
+            ("1.2", Some((vec![1, 2], None))),
+            ("1.2.", Some((vec![1, 2], None))),
+            ("1.2.3", Some((vec![1, 2, 3], None))),
+            ("1.2.3.", Some((vec![1, 2, 3], None))),
+            ("1.2.3.  ", Some((vec![1, 2, 3], None))),
+            ("   1.2.3.", Some((vec![1, 2, 3], None))),
+            ("   1.2.3.  ", Some((vec![1, 2, 3], None))),
+            ("1.2.3.4", Some((vec![1, 2, 3, 4], None))),
+            (
+                "1.2.3.4.5.6.7.8.9",
+                Some((vec![1, 2, 3, 4, 5, 6, 7, 8, 9], None)),
+            ),
+            ("3.16.0_rc1", Some((vec![3, 16, 0], Some("_rc1")))),
+            (
+                "21.05pre275822.916ee862e87",
+                Some((vec![21, 5], Some("pre275822.916ee862e87"))),
+            ),
+            ("1.2.beta", None),
+            ("1..2", None),
+            ("7 (Core)", None),
+            ("99999999999999999999", None),
         ];
 
         for (s, expected) in &data {
             let result = parse_version(s);
-            assert_eq!(expected, &result);
+            assert_eq!(expected, &result, "{:?}", s);
         }
     }
 
@@ -117,6 +438,19 @@ mod tests {
         let data = [
             ("", Version::Unknown),
             ("1.2.3", Version::Semantic(1, 2, 3)),
+            ("7.9.2009", Version::Semantic(7, 9, 2009)),
+            (
+                "10.0.19045.3570",
+                Version::Extended(vec![10, 0, 19045, 3570], None),
+            ),
+            (
+                "3.16.0_rc1",
+                Version::Extended(vec![3, 16, 0], Some("_rc1".to_owned())),
+            ),
+            (
+                "21.05pre275822.916ee862e87",
+                Version::Extended(vec![21, 5], Some("pre275822.916ee862e87".to_owned())),
+            ),
             (custom_version, Version::Custom(custom_version.to_owned())),
         ];
 
@@ -141,10 +475,146 @@ mod tests {
                 Version::Rolling(Some("date".to_owned())),
                 "Rolling Release (date)",
             ),
+            (
+                Version::Extended(vec![10, 0, 19045, 3570], None),
+                "10.0.19045.3570",
+            ),
+            (
+                Version::Extended(vec![3, 16, 0], Some("_rc1".to_owned())),
+                "3.16.0_rc1",
+            ),
         ];
 
         for (version, expected) in &data {
             assert_eq!(expected, &version.to_string());
         }
     }
+
+    #[test]
+    fn accessors() {
+        let data = [
+            (Version::Unknown, None, None, None, None),
+            (Version::Semantic(1, 2, 3), Some(1), Some(2), Some(3), None),
+            (
+                Version::Extended(vec![10, 0, 19045, 3570], None),
+                Some(10),
+                Some(0),
+                Some(19045),
+                None,
+            ),
+            (
+                Version::Extended(vec![3, 16], Some("_rc1".to_owned())),
+                Some(3),
+                Some(16),
+                Some(0),
+                Some("rc1"),
+            ),
+            (
+                Version::Custom("2018.03".to_owned()),
+                Some(2018),
+                Some(3),
+                Some(0),
+                None,
+            ),
+            (Version::Custom("XX".to_owned()), None, None, None, None),
+            (Version::Rolling(None), None, None, None, None),
+        ];
+
+        for (version, major, minor, patch, pre_release) in &data {
+            assert_eq!(version.major(), *major, "{:?}", version);
+            assert_eq!(version.minor(), *minor, "{:?}", version);
+            assert_eq!(version.patch(), *patch, "{:?}", version);
+            assert_eq!(version.pre_release(), *pre_release, "{:?}", version);
+        }
+    }
+
+    #[test]
+    fn ordering() {
+        // Each version is strictly greater than the previous one.
+        let versions = [
+            Version::Unknown,
+            Version::Semantic(1, 2, 0),
+            Version::Extended(vec![1, 2], None),
+            Version::Custom("1.2.1".to_owned()),
+            Version::Extended(vec![3, 16, 0], Some("_rc1".to_owned())),
+            Version::Extended(vec![3, 16, 0], Some("_rc2".to_owned())),
+            Version::Extended(vec![3, 16, 0], Some("_rc10".to_owned())),
+            Version::Semantic(3, 16, 0),
+            Version::Extended(vec![3, 16, 0], Some("-RELEASE".to_owned())),
+            Version::Extended(vec![3, 16, 0], Some("-RELEASE-p3".to_owned())),
+            Version::Custom("9.10".to_owned()),
+            Version::Semantic(10, 0, 0),
+            Version::Extended(vec![10, 0, 19044, 2006], None),
+            Version::Semantic(10, 0, 19045),
+            Version::Extended(vec![10, 0, 19045, 3570], None),
+            Version::Custom("A.B.C".to_owned()),
+            Version::Custom("XX".to_owned()),
+            Version::Rolling(None),
+            Version::Rolling(Some("2020.05.24".to_owned())),
+        ];
+
+        for (i, a) in versions.iter().enumerate() {
+            for (j, b) in versions.iter().enumerate() {
+                assert_eq!(a.cmp(b), i.cmp(&j), "{:?} vs {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn bsd_ordering() {
+        // Each version is strictly greater than the previous one.
+        let versions = [
+            "13.1-BETA1",
+            "13.1-RC2",
+            "13.1",
+            "13.1-RELEASE",
+            "13.1-RELEASE-p3",
+            "13.1-RELEASE-p10",
+            "13.2-RC1",
+            "13.2-RELEASE",
+        ];
+
+        for pair in versions.windows(2) {
+            let (a, b) = (Version::from_string(pair[0]), Version::from_string(pair[1]));
+            assert!(a < b, "{:?} vs {:?}", a, b);
+            assert_eq!(a.cmp_value(&b), Ordering::Less, "{:?} vs {:?}", a, b);
+        }
+        assert!(Version::from_string("7.4") < Version::from_string("7.4-p2"));
+    }
+
+    #[test]
+    fn release_suffixes() {
+        let data = [
+            ("RELEASE", true),
+            ("release", true),
+            ("RELEASE-p3", true),
+            ("p2", true),
+            ("RELEASE-rc1", false),
+            ("RELEASED", false),
+            ("pre275822", false),
+            ("p", false),
+            ("rc1", false),
+            ("BETA1", false),
+        ];
+
+        for (suffix, expected) in &data {
+            assert_eq!(is_release(suffix), *expected, "{}", suffix);
+        }
+    }
+
+    #[test]
+    fn natural_ordering() {
+        let data = [
+            ("", "", Ordering::Equal),
+            ("rc1", "rc1", Ordering::Equal),
+            ("rc01", "rc1", Ordering::Equal),
+            ("rc2", "rc10", Ordering::Less),
+            ("alpha", "beta", Ordering::Less),
+            ("beta", "beta1", Ordering::Less),
+        ];
+
+        for (a, b, expected) in &data {
+            assert_eq!(natural_cmp(a, b), *expected, "{:?} vs {:?}", a, b);
+        }
+    }
 }
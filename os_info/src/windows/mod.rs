@@ -2,10 +2,10 @@ mod winapi;
 
 use log::trace;
 
-use crate::Info;
+use crate::{Detector, Info};
 
-pub fn current_platform() -> Info {
-    trace!("windows::current_platform is called");
+pub fn current_platform(detector: &Detector) -> Info {
+    trace!("windows::current_platform({:?}) is called", detector);
     let info = winapi::get();
     trace!("Returning {:?}", info);
     info
@@ -19,7 +19,7 @@ mod tests {
 
     #[test]
     fn os_type() {
-        let version = current_platform();
+        let version = current_platform(&Detector::default());
         assert_eq!(Type::Windows, version.os_type());
         assert!(version.edition().is_some());
     }
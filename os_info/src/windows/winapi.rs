@@ -21,17 +21,20 @@ use winapi::{
     },
     um::{
         libloaderapi::{GetModuleHandleA, GetProcAddress},
-        sysinfoapi::{GetSystemInfo, SYSTEM_INFO},
+        sysinfoapi::{GetNativeSystemInfo, GetSystemInfo, SYSTEM_INFO},
         winnt::{
-            KEY_READ, PROCESSOR_ARCHITECTURE_AMD64, REG_SZ, VER_NT_WORKSTATION,
-            VER_SUITE_WH_SERVER, WCHAR,
+            KEY_READ, PROCESSOR_ARCHITECTURE_AMD64, PROCESSOR_ARCHITECTURE_ARM,
+            PROCESSOR_ARCHITECTURE_ARM64, PROCESSOR_ARCHITECTURE_INTEL, REG_DWORD, REG_SZ,
+            VER_NT_WORKSTATION, VER_SUITE_WH_SERVER, WCHAR,
         },
-        winreg::{RegOpenKeyExW, RegQueryValueExW, HKEY_LOCAL_MACHINE, LSTATUS},
+        winreg::{RegCloseKey, RegOpenKeyExW, RegQueryValueExW, HKEY_LOCAL_MACHINE, LSTATUS},
         winuser::{GetSystemMetrics, SM_SERVERR2},
     },
 };
 
-use crate::{Bitness, Info, Type, Version};
+use crate::{
+    Architecture, Bitness, DetectionError, Info, Provenance, Source, SourceStatus, Type, Version,
+};
 
 #[cfg(target_arch = "x86")]
 type OSVERSIONINFOEX = winapi::um::winnt::OSVERSIONINFOEXA;
@@ -41,26 +44,47 @@ type OSVERSIONINFOEX = winapi::um::winnt::OSVERSIONINFOEXW;
 
 pub fn get() -> Info {
     let (version, edition) = version();
-    Info {
+
+    let mut provenance = Provenance::default();
+    if version == Version::Unknown {
+        let error = DetectionError::SystemCallFailed {
+            name: "RtlGetVersion".to_owned(),
+            message: "unable to get the version information".to_owned(),
+        };
+        provenance.record_error(Source::SystemApi, "RtlGetVersion", error);
+        provenance.record_target();
+    } else {
+        provenance.record(Source::SystemApi, "RtlGetVersion", SourceStatus::Parsed);
+    }
+
+    let mut info = Info {
         os_type: Type::Windows,
         version,
         edition,
         bitness: bitness(),
+        architecture: architecture(),
         ..Default::default()
-    }
+    };
+    provenance.select(&info);
+    info.provenance = provenance;
+    info
 }
 
 fn version() -> (Version, Option<String>) {
     match version_info() {
         None => (Version::Unknown, None),
-        Some(v) => (
-            Version::Semantic(
+        Some(v) => {
+            let (major, minor, build) = (
                 v.dwMajorVersion as u64,
                 v.dwMinorVersion as u64,
                 v.dwBuildNumber as u64,
-            ),
-            product_name().or_else(|| edition(&v)),
-        ),
+            );
+            let version = match update_build_revision() {
+                Some(ubr) => Version::Extended(vec![major, minor, build, ubr], None),
+                None => Version::Semantic(major, minor, build),
+            };
+            (version, product_name().or_else(|| edition(&v)))
+        }
     }
 }
 
@@ -104,6 +128,21 @@ fn bitness() -> Bitness {
     }
 }
 
+// GetNativeSystemInfo reports the architecture of the operating system even when called from a
+// WOW64 or an emulated process.
+fn architecture() -> Architecture {
+    let mut info: SYSTEM_INFO = unsafe { mem::zeroed() };
+    unsafe { GetNativeSystemInfo(&mut info) };
+
+    match unsafe { info.u.s().wProcessorArchitecture } {
+        PROCESSOR_ARCHITECTURE_INTEL => Architecture::X86,
+        PROCESSOR_ARCHITECTURE_AMD64 => Architecture::X86_64,
+        PROCESSOR_ARCHITECTURE_ARM => Architecture::Arm,
+        PROCESSOR_ARCHITECTURE_ARM64 => Architecture::Aarch64,
+        _ => Architecture::Unknown,
+    }
+}
+
 // Calls the Win32 API function RtlGetVersion to get the OS version information:
 // https://msdn.microsoft.com/en-us/library/mt723418(v=vs.85).aspx
 fn version_info() -> Option<OSVERSIONINFOEX> {
@@ -193,6 +232,44 @@ fn product_name() -> Option<String> {
     )
 }
 
+// Reads the update build revision (the fourth component of the version, for example `3570` in
+// `10.0.19045.3570`), which isn't reported by RtlGetVersion.
+fn update_build_revision() -> Option<u64> {
+    const REG_SUCCESS: LSTATUS = ERROR_SUCCESS as LSTATUS;
+
+    let sub_key = to_wide("SOFTWARE\\Microsoft\\Windows NT\\CurrentVersion");
+    let mut key = ptr::null_mut();
+    if unsafe { RegOpenKeyExW(HKEY_LOCAL_MACHINE, sub_key.as_ptr(), 0, KEY_READ, &mut key) }
+        != REG_SUCCESS
+        || key.is_null()
+    {
+        log::error!("RegOpenKeyExW(HKEY_LOCAL_MACHINE, ...) failed");
+        return None;
+    }
+
+    let name = to_wide("UBR");
+    let mut data_type: DWORD = 0;
+    let mut data: DWORD = 0;
+    let mut data_size = mem::size_of::<DWORD>() as DWORD;
+    let status = unsafe {
+        RegQueryValueExW(
+            key,
+            name.as_ptr(),
+            ptr::null_mut(),
+            &mut data_type,
+            &mut data as *mut DWORD as LPBYTE,
+            &mut data_size,
+        )
+    };
+    unsafe { RegCloseKey(key) };
+    if status != REG_SUCCESS || data_type != REG_DWORD {
+        log::debug!("UBR registry value isn't available");
+        return None;
+    }
+
+    Some(data.into())
+}
+
 fn to_wide(value: &str) -> Vec<WCHAR> {
     OsStr::new(value).encode_wide().chain(Some(0)).collect()
 }
@@ -355,6 +432,11 @@ mod tests {
         assert!(!edition.is_empty());
     }
 
+    #[test]
+    fn get_update_build_revision() {
+        assert!(update_build_revision().is_some());
+    }
+
     #[test]
     fn to_wide_str() {
         let data = [
@@ -2,3 +2,11 @@
 fn get() {
     let _ = os_info::get();
 }
+
+#[test]
+fn try_get() {
+    match os_info::try_get() {
+        Ok(info) => assert_eq!(info, os_info::get()),
+        Err(e) => assert!(os_info::get().provenance().selected().is_some(), "{}", e),
+    }
+}